@@ -1,145 +1,191 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use advent_of_code_2023::solver;
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser, Debug)]
-#[command(author, version)]
+#[command(
+    author,
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub struct Args {
+    /// Year of the problems to solve.
+    #[arg(long, global = true, default_value_t = 2023)]
+    pub year: u16,
+
+    /// Day of the problem to solve.
+    #[arg(long, required = true)]
+    pub day: Option<u8>,
+
+    /// Part of the problem to solve. Solving both parts prints a table of the answers and
+    /// runtimes.
+    #[arg(long, value_enum, default_value_t = Part::Both)]
+    pub part: Part,
+
+    /// Path to the puzzle input, or `-` to read it from stdin.
+    /// Defaults to the input imported in the inputs directory.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub input: Option<PathBuf>,
+
+    /// Directory of the puzzle inputs imported with the `inputs` subcommand, as
+    /// `<DIR>/<YEAR>/dayN.txt`. Defaults to a directory of the user's, so that the inputs are found
+    /// from anywhere.
+    #[arg(
+        long,
+        global = true,
+        env = "AOC_INPUTS_DIR",
+        value_name = "DIR",
+        default_value_os_t = advent_of_code_2023::default_inputs_dir()
+    )]
+    pub inputs_dir: PathBuf,
+
+    /// Trace the intermediate state of the solvers to stderr.
+    #[arg(short = 'v', long, global = true)]
+    pub trace: bool,
+
+    /// Format of the answers.
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
     #[command(subcommand)]
-    pub day: Day,
+    pub command: Option<Command>,
 }
 
 #[derive(Subcommand, Debug)]
-pub enum Day {
-    /// Solve day 1's problem.
-    Day1 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 2's problem.
-    Day2 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 3's problem.
-    Day3 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 4's problem.
-    Day4 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 5's problem.
-    Day5 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 6's problem.
-    Day6 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 7's problem.
-    Day7 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 8's problem.
-    Day8 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 9's problem.
-    Day9 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 10's problem.
-    Day10 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 11's problem.
-    Day11 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 12's problem.
-    Day12 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 13's problem.
-    Day13 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 14's problem.
-    Day14 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 15's problem.
-    Day15 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 16's problem.
-    Day16 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 17's problem.
-    Day17 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 18's problem.
-    Day18 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 19's problem.
-    Day19 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 20's problem.
-    Day20 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 21's problem.
-    Day21 {
-        #[command(subcommand)]
-        part: Part,
-    },
-    /// Solve day 22's problem.
-    Day22 {
-        #[command(subcommand)]
-        part: Part,
+pub enum Command {
+    /// Solve both parts of every day's problem, and summarize their answers and runtimes.
+    All {
+        /// Number of parts to solve in parallel. Parts solved in parallel compete for the CPU,
+        /// which makes their runtimes less reliable.
+        #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+    },
+    /// Solve the problems and check their answers against the ones recorded next to the inputs,
+    /// in `<DIR>/<YEAR>/dayN.answers.toml`. Exits with an error if any answer is wrong.
+    Verify {
+        /// Day of the problem to verify. Defaults to every day.
+        #[arg(long)]
+        day: Option<u8>,
+    },
+    /// Solve the problems several times, and report the statistics of their runtimes and how
+    /// much they allocate.
+    Bench(BenchArgs),
+    /// Sum the calibration values of day 1's input, with the digits written as digits or spelled
+    /// out with the words of a vocabulary.
+    Calibrate {
+        /// Vocabulary of the spelled-out digits: one of the built-in `none`, `english`, `french`,
+        /// `spanish` and `german`, or the path to a file of `word = digit` lines.
+        #[arg(long, value_name = "NAME|PATH", default_value = "english")]
+        vocabulary: String,
+
+        /// List the first and last digits found in each line, with their byte offsets and the
+        /// resulting calibration value. Lines without a digit are flagged instead of failing, but
+        /// still exit with an error.
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Sum the IDs of the games of day 2's input which are possible with a bag of cubes.
+    Cubes {
+        /// Cubes in the bag, of any colours, written like a draw.
+        #[arg(long, default_value = "12 red, 13 green, 14 blue")]
+        bag: String,
+
+        /// List the minimal bag of each game instead, with its power, like part 2.
+        #[arg(long, conflicts_with = "bag")]
+        minimal_bags: bool,
     },
-    /// Solve day 23's problem.
-    Day23 {
+    /// Manage the puzzle inputs imported in the inputs directory, which is kept out of the
+    /// source tree.
+    Inputs {
         #[command(subcommand)]
-        part: Part,
+        command: InputsCommand,
     },
-    /// Solve day 24's problem.
-    Day24 {
-        #[command(subcommand)]
-        part: Part,
+}
+
+// The options of the `bench` subcommand, which has too many of them to pass around separately.
+#[derive(clap::Args, Debug)]
+pub struct BenchArgs {
+    /// Day of the problem to benchmark. Defaults to every day.
+    #[arg(long)]
+    pub day: Option<u8>,
+
+    /// Part of the problem to benchmark.
+    #[arg(long, value_enum, default_value_t = Part::Both)]
+    pub part: Part,
+
+    /// Number of timed runs of each part, after an untimed warm-up run.
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub runs: u32,
+
+    /// Save the results to a JSON file, as a baseline to compare later runs against.
+    #[arg(long, value_name = "PATH")]
+    pub save: Option<PathBuf>,
+
+    /// Compare the median runtimes against a baseline saved with `--save`. Exits with an
+    /// error if any of them regressed by more than the threshold.
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+
+    /// Percentage by which a median runtime may increase before it is a regression.
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum InputsCommand {
+    /// List the input of every day, if there is one.
+    List,
+    /// Validate the input of a day with its parser, and copy it to the inputs directory.
+    Import {
+        /// Day of the problem the input is for.
+        #[arg(long)]
+        day: u8,
+
+        /// Path to the puzzle input, or `-` to read it from stdin.
+        path: PathBuf,
+
+        /// Replace the input already imported for the day.
+        #[arg(long)]
+        force: bool,
     },
-    /// Solve day 25's problem.
-    Day25 {
-        #[command(subcommand)]
-        part: Part,
+    /// Validate the inputs with the parsers of their days. Exits with an error if any of them is
+    /// invalid.
+    Validate {
+        /// Day of the input to validate. Defaults to every day.
+        #[arg(long)]
+        day: Option<u8>,
     },
 }
 
-#[derive(Subcommand, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// The answer of a single part, or a table of the answers and runtimes.
+    Text,
+    /// A JSON object per part, with its answer and runtime in nanoseconds. Commands that solve
+    /// several days print an array of these objects instead.
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
 pub enum Part {
     /// Solve the 1st part of the problem.
+    #[value(name = "1")]
     Part1,
     /// Solve the 2nd part of the problem.
+    #[value(name = "2")]
     Part2,
+    /// Solve both parts of the problem.
+    Both,
+}
+
+impl Part {
+    pub fn parts(&self) -> &'static [solver::Part] {
+        match self {
+            Part::Part1 => &[solver::Part::Part1],
+            Part::Part2 => &[solver::Part::Part2],
+            Part::Both => &[solver::Part::Part1, solver::Part::Part2],
+        }
+    }
 }
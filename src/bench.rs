@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2023::registry::SolverEntry;
+use advent_of_code_2023::Part;
+use serde_json::{json, Value};
+
+use crate::allocations::Allocations;
+use crate::runner::{self, Outcome, Run};
+
+// Statistics of the timed runs of a part.
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    // The allocations of a single run.
+    pub allocations: Allocations,
+}
+
+impl Stats {
+    // The percentiles are those of the nearest rank.
+    fn new(mut elapsed: Vec<Duration>, allocations: Allocations) -> Self {
+        elapsed.sort();
+        let percentile = |percent: usize| elapsed[(elapsed.len() * percent).div_ceil(100) - 1];
+
+        Self {
+            runs: elapsed.len(),
+            min: elapsed[0],
+            median: percentile(50),
+            p95: percentile(95),
+            allocations,
+        }
+    }
+}
+
+pub struct Bench {
+    // The warm-up run, which tells why a part couldn't be benchmarked.
+    pub run: Run,
+    pub stats: Option<Stats>,
+    // The median runtime of the part in the baseline compared against, if any.
+    pub baseline_median: Option<Duration>,
+}
+
+// Solves the part once to warm up, then times `runs` more runs of it. Parts that can't be
+// solved, e.g. because they don't terminate, are only reported.
+pub fn bench(solver: &SolverEntry, part: Part, input: &io::Result<String>, runs: usize) -> Bench {
+    let run = runner::run(solver, part, input);
+    let stats = match (&run.outcome, input) {
+        (Outcome::Solved { .. }, Ok(input)) => {
+            let mut elapsed = Vec::with_capacity(runs);
+            let mut allocations = Allocations::default();
+            for _ in 0..runs {
+                let allocations_before = Allocations::so_far();
+                let start = Instant::now();
+                // The input was already solved, so it can't be invalid.
+                let _ = solver.solve(part, input);
+                elapsed.push(start.elapsed());
+                allocations = Allocations::so_far() - allocations_before;
+            }
+
+            Some(Stats::new(elapsed, allocations))
+        }
+        _ => None,
+    };
+
+    Bench {
+        run,
+        stats,
+        baseline_median: None,
+    }
+}
+
+impl Bench {
+    // How much slower the median runtime is than the baseline's, e.g. 0.1 for 10% slower.
+    fn change(&self) -> Option<f64> {
+        let median = self.stats.as_ref()?.median.as_secs_f64();
+        let baseline_median = self.baseline_median?.as_secs_f64();
+
+        Some(median / baseline_median - 1.0)
+    }
+
+    // A part regressed if its median runtime increased by more than the threshold percentage.
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change()
+            .is_some_and(|change| change * 100.0 > threshold)
+    }
+
+    // The warm-up run with the statistics of the timed runs, which are null for parts that
+    // couldn't be benchmarked. This is also the format of a saved baseline.
+    pub fn to_json(&self) -> Value {
+        let mut bench = self.run.to_json();
+        let stats = self.stats.as_ref();
+        let nanos = |duration: Duration| duration.as_nanos() as u64;
+        bench["runs"] = json!(stats.map(|stats| stats.runs));
+        bench["min_ns"] = json!(stats.map(|stats| nanos(stats.min)));
+        bench["median_ns"] = json!(stats.map(|stats| nanos(stats.median)));
+        bench["p95_ns"] = json!(stats.map(|stats| nanos(stats.p95)));
+        bench["allocations"] = json!(stats.map(|stats| stats.allocations.count));
+        bench["allocated_bytes"] = json!(stats.map(|stats| stats.allocations.bytes));
+        if let Some(baseline_median) = self.baseline_median {
+            bench["baseline_median_ns"] = json!(nanos(baseline_median));
+        }
+
+        bench
+    }
+
+    fn change_cell(&self, threshold: f64) -> String {
+        match self.change() {
+            Some(change) if self.regressed(threshold) => {
+                format!("{:+.1}% REGRESSION", change * 100.0)
+            }
+            Some(change) => format!("{:+.1}%", change * 100.0),
+            None => "-".to_owned(),
+        }
+    }
+}
+
+// Reads the median runtimes of a baseline saved with `--save`, keyed by day and part.
+pub fn read_baseline(path: &Path) -> io::Result<HashMap<(u8, u8), Duration>> {
+    let invalid = |error: &str| io::Error::new(io::ErrorKind::InvalidData, error.to_owned());
+    let baseline: Value = serde_json::from_str(&std::fs::read_to_string(path)?)
+        .map_err(|error| invalid(&error.to_string()))?;
+    let benches = baseline
+        .as_array()
+        .ok_or_else(|| invalid("expected an array of benchmarks"))?;
+
+    let mut medians = HashMap::new();
+    for bench in benches {
+        let field = |name: &str| bench.get(name).and_then(Value::as_u64);
+        let (Some(day), Some(part)) = (field("day"), field("part")) else {
+            return Err(invalid("expected a benchmark with a day and a part"));
+        };
+        // Parts that couldn't be benchmarked have no median.
+        if let Some(median) = field("median_ns") {
+            medians.insert((day as u8, part as u8), Duration::from_nanos(median));
+        }
+    }
+
+    Ok(medians)
+}
+
+pub fn print_benches(benches: &[Bench], threshold: f64) {
+    let compared = benches.iter().any(|bench| bench.baseline_median.is_some());
+    let rows: Vec<_> = benches
+        .iter()
+        .map(|bench| {
+            let run = &bench.run;
+            let duration = |duration: fn(&Stats) -> Duration| {
+                bench.stats.as_ref().map_or_else(
+                    || "-".to_owned(),
+                    |stats| format!("{:.2?}", duration(stats)),
+                )
+            };
+            let (runs, allocations) = match &bench.stats {
+                Some(stats) => (
+                    stats.runs.to_string(),
+                    format!(
+                        "{} ({} B)",
+                        stats.allocations.count, stats.allocations.bytes
+                    ),
+                ),
+                // Explain why the part couldn't be benchmarked.
+                None => ("-".to_owned(), run.answer_cell()),
+            };
+            [
+                run.day.to_string(),
+                run.part.number().to_string(),
+                runs,
+                duration(|stats| stats.min),
+                duration(|stats| stats.median),
+                duration(|stats| stats.p95),
+                allocations,
+                bench.change_cell(threshold),
+            ]
+        })
+        .collect();
+
+    if compared {
+        runner::print_table(
+            [
+                "Day",
+                "Part",
+                "Runs",
+                "Min",
+                "Median",
+                "p95",
+                "Allocations",
+                "vs baseline",
+            ],
+            &rows,
+            &[6, 7],
+        );
+
+        let regressions = benches
+            .iter()
+            .filter(|bench| bench.regressed(threshold))
+            .count();
+        println!(
+            "\n{regressions} of {} parts regressed by more than {threshold}%.",
+            benches.len()
+        );
+    } else {
+        let rows: Vec<_> = rows
+            .into_iter()
+            .map(|[day, part, runs, min, median, p95, allocations, _]| {
+                [day, part, runs, min, median, p95, allocations]
+            })
+            .collect();
+        runner::print_table(
+            ["Day", "Part", "Runs", "Min", "Median", "p95", "Allocations"],
+            &rows,
+            &[6],
+        );
+    }
+}
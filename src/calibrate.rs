@@ -0,0 +1,70 @@
+use advent_of_code_2023::day1::{Calibration, Scanner, Token, Vocabulary};
+use serde_json::{json, Value};
+
+use crate::runner;
+
+// Returns the built-in vocabulary of the given name, or reads it from the file at that path.
+pub fn read_vocabulary(name_or_path: &str) -> Result<Vocabulary, String> {
+    if let Some(vocabulary) = Vocabulary::built_in(name_or_path) {
+        return Ok(vocabulary);
+    }
+
+    let text = std::fs::read_to_string(name_or_path).map_err(|error| {
+        let built_in = Vocabulary::BUILT_IN.join(", ");
+        format!("{error}, and it isn't one of the built-in vocabularies ({built_in})")
+    })?;
+    Vocabulary::parse(&text).map_err(|error| {
+        let error = error.locate(1, &text);
+        format!(
+            "line {}, column {}: expected {}, found `{}`",
+            error.line, error.column, error.expected, error.text
+        )
+    })
+}
+
+// The calibration of each line of the input, or `None` for lines without a digit.
+pub fn explain<'a>(input: &'a str, scanner: &Scanner) -> Vec<Option<Calibration<'a>>> {
+    input.lines().map(|line| scanner.calibrate(line)).collect()
+}
+
+// Lines without a digit have a null `first`, `last` and `value`.
+pub fn to_json(line: usize, calibration: &Option<Calibration>) -> Value {
+    let token = |token: Token| json!({ "text": token.text, "offset": token.offset });
+    json!({
+        "line": line,
+        "first": calibration.map(|calibration| token(calibration.first)),
+        "last": calibration.map(|calibration| token(calibration.last)),
+        "value": calibration.map(|calibration| calibration.value()),
+    })
+}
+
+pub fn print_explanations(calibrations: &[Option<Calibration>]) {
+    let token_cell = |token: Token| format!("{} @ {}", token.text, token.offset);
+    let rows: Vec<_> = calibrations
+        .iter()
+        .enumerate()
+        .map(|(index, calibration)| match calibration {
+            Some(calibration) => [
+                (index + 1).to_string(),
+                token_cell(calibration.first),
+                token_cell(calibration.last),
+                calibration.value().to_string(),
+            ],
+            None => [
+                (index + 1).to_string(),
+                "-".to_owned(),
+                "-".to_owned(),
+                "NO DIGIT".to_owned(),
+            ],
+        })
+        .collect();
+    runner::print_table(["Line", "First", "Last", "Value"], &rows, &[1, 2]);
+
+    let sum: usize = calibrations.iter().flatten().map(Calibration::value).sum();
+    let without_digit = calibrations.iter().filter(|c| c.is_none()).count();
+    println!("\nSum: {sum}");
+    println!(
+        "{without_digit} of {} lines have no digit.",
+        calibrations.len()
+    );
+}
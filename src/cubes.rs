@@ -0,0 +1,32 @@
+use advent_of_code_2023::day2::Game;
+use serde_json::{json, Value};
+
+use crate::runner;
+
+// The minimal bag of the game, counted by colour, with its power.
+pub fn to_json(game: &Game) -> Value {
+    let bag = game.minimal_bag();
+    json!({
+        "game": game.id,
+        "bag": bag.cubes,
+        "power": bag.power(),
+    })
+}
+
+pub fn print_minimal_bags(games: &[Game]) {
+    let rows: Vec<_> = games
+        .iter()
+        .map(|game| {
+            let bag = game.minimal_bag();
+            [
+                game.id.to_string(),
+                bag.to_string(),
+                bag.power().to_string(),
+            ]
+        })
+        .collect();
+    runner::print_table(["Game", "Minimal bag", "Power"], &rows, &[1]);
+
+    let total_power: u64 = games.iter().map(|game| game.minimal_bag().power()).sum();
+    println!("\nTotal power: {total_power}");
+}
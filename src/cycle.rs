@@ -0,0 +1,186 @@
+//! Detection of the cycles of simulations, to skip ahead to a step far away.
+//!
+//! A simulation is a sequence of states, each one computed from the previous one by `next`. If
+//! there are finitely many states, the sequence eventually repeats itself: this is its cycle. All
+//! the functions below loop forever on sequences which never repeat.
+
+use hashbrown::HashMap;
+use std::hash::Hash;
+
+/// The cycle of a sequence of states: from step `start` onwards, the states repeat every `length`
+/// steps. The initial state is step 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Returns the earliest step whose state is the same as the given step.
+    pub fn fast_forward(self, step: usize) -> usize {
+        if step < self.start {
+            step
+        } else {
+            self.start + (step - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle with Floyd's "tortoise and hare" algorithm, which keeps only two states.
+pub fn floyd<T: Clone + PartialEq>(initial: T, mut next: impl FnMut(&T) -> T) -> Cycle {
+    // The hare moves twice as fast as the tortoise, so they meet once both are in the cycle.
+    let mut tortoise = next(&initial);
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        let hare_step = next(&hare);
+        hare = next(&hare_step);
+    }
+
+    // The hare is now as many steps from the start of the cycle as the initial state is.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = next(&tortoise);
+    while tortoise != hare {
+        hare = next(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle with Brent's algorithm, which keeps only two states like Floyd's, but usually
+/// computes fewer of them.
+pub fn brent<T: Clone + PartialEq>(initial: T, mut next: impl FnMut(&T) -> T) -> Cycle {
+    // The tortoise teleports to the hare at every power of 2, until the hare comes back to it.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = next(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+
+    // With the hare a cycle ahead of the tortoise, they meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle by remembering every state until one repeats, which computes each state
+/// once. Returns the cycle with the states of the steps before it repeats.
+pub fn find_cycle<T: Clone + Eq + Hash>(
+    initial: T,
+    mut next: impl FnMut(&T) -> T,
+) -> (Cycle, Vec<T>) {
+    let mut steps = HashMap::new();
+    let mut states = vec![initial];
+    loop {
+        let state = states.last().unwrap();
+        let step = states.len() - 1;
+        if let Some(start) = steps.insert(state.clone(), step) {
+            states.pop();
+            let cycle = Cycle {
+                start,
+                length: step - start,
+            };
+            return (cycle, states);
+        }
+
+        states.push(next(state));
+    }
+}
+
+/// Returns the state at the given step, fast-forwarding through the cycle once it is found.
+pub fn nth<T: Clone + Eq + Hash>(initial: T, mut next: impl FnMut(&T) -> T, step: usize) -> T {
+    let mut steps = HashMap::new();
+    let mut states = vec![initial];
+    while states.len() <= step {
+        let state = states.last().unwrap();
+        if let Some(start) = steps.insert(state.clone(), states.len() - 1) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - 1 - start,
+            };
+            crate::trace!(
+                "found a cycle of length {} from step {}, fast-forwarding to step {step}",
+                cycle.length,
+                cycle.start
+            );
+            return states.swap_remove(cycle.fast_forward(step));
+        }
+
+        states.push(next(state));
+    }
+
+    states.swap_remove(step)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, 3, 4, 5, 3, 4, 5, ...
+    fn next(state: &u32) -> u32 {
+        if *state == 5 {
+            3
+        } else {
+            state + 1
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 3,
+        length: 3,
+    };
+
+    #[test]
+    fn algorithms_find_the_same_cycle() {
+        assert_eq!(floyd(0, next), CYCLE);
+        assert_eq!(brent(0, next), CYCLE);
+
+        let (cycle, states) = find_cycle(0, next);
+        assert_eq!(cycle, CYCLE);
+        assert_eq!(states, [0, 1, 2, 3, 4, 5]);
+
+        // Starting within the cycle.
+        let cycle = Cycle {
+            start: 0,
+            length: 3,
+        };
+        assert_eq!(floyd(4, next), cycle);
+        assert_eq!(brent(4, next), cycle);
+    }
+
+    #[test]
+    fn fast_forward_to_a_far_step() {
+        assert_eq!(CYCLE.fast_forward(2), 2);
+        assert_eq!(CYCLE.fast_forward(1_000_000_000), 4);
+        assert_eq!(nth(0, next, 1_000_000_000), 4);
+        assert_eq!(nth(0, next, 4), 4);
+        assert_eq!(nth(0, next, 0), 0);
+    }
+}
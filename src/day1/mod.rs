@@ -1,66 +1,318 @@
-use crate::solver::Solver;
-use regex::{Captures, Regex};
-
-lazy_static! {
-    static ref PART_1_FIRST_DIGIT_CALIBRATION_RE: Regex = Regex::new(r"^\D*?(\d)").unwrap();
-    static ref PART_1_SECOND_DIGIT_CALIBRATION_RE: Regex = Regex::new(r"^.*(\d).*?$").unwrap();
-    static ref PART_2_FIRST_DIGIT_CALIBRATION_RE: Regex =
-        Regex::new(r"^.*?(\d|one|two|three|four|five|six|seven|eight|nine).*$").unwrap();
-    static ref PART_2_SECOND_DIGIT_CALIBRATION_RE: Regex =
-        Regex::new(r"^.*(\d|one|two|three|four|five|six|seven|eight|nine).*?$").unwrap();
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
+
+/// The words spelling out the digits 1 to 9, in order, in each of the built-in vocabularies.
+const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const FRENCH: [&str; 9] = [
+    "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+const SPANISH: [&str; 9] = [
+    "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+const GERMAN: [&str; 9] = [
+    "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+
+/// The words spelling out digits, each with the digit it spells out.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, usize)>,
 }
-pub struct Day1Solver {}
-impl Day1Solver {
-    fn parse_calibration(captures: &Captures) -> usize {
-        match captures.get(1).unwrap().as_str() {
-            "one" => 1,
-            "two" => 2,
-            "three" => 3,
-            "four" => 4,
-            "five" => 5,
-            "six" => 6,
-            "seven" => 7,
-            "eight" => 8,
-            "nine" => 9,
-            capture => capture.parse().unwrap(),
+
+impl Vocabulary {
+    /// The names of the built-in vocabularies. `none` has no words, for the digits written as
+    /// digits only.
+    pub const BUILT_IN: [&'static str; 5] = ["none", "english", "french", "spanish", "german"];
+
+    /// Returns the built-in vocabulary of the given name, if there is one.
+    pub fn built_in(name: &str) -> Option<Self> {
+        let words = match name {
+            "none" => return Some(Self::default()),
+            "english" => ENGLISH,
+            "french" => FRENCH,
+            "spanish" => SPANISH,
+            "german" => GERMAN,
+            _ => return None,
+        };
+
+        Some(Self::from_digits_1_to_9(words))
+    }
+
+    pub fn english() -> Self {
+        Self::from_digits_1_to_9(ENGLISH)
+    }
+
+    fn from_digits_1_to_9(words: [&str; 9]) -> Self {
+        let words = (1..)
+            .zip(words)
+            .map(|(digit, word)| (word.to_owned(), digit));
+        Self {
+            words: words.collect(),
+        }
+    }
+
+    /// Parses a vocabulary of a word per line, e.g. `eins = 1`. Words may spell out any digit,
+    /// from 0 to 9, and several words may spell out the same digit. Empty lines are ignored.
+    pub fn parse(text: &str) -> ParseResult<Self> {
+        let mut words = Vec::new();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let (word, digit) = parse::split_once(line, "=")?;
+            let word = word.trim();
+            if word.is_empty() {
+                return Err(ParseError::new(word, "a word"));
+            }
+            let digit_text = digit.trim();
+            let digit: usize = parse::number(digit_text)?;
+            if digit > 9 {
+                return Err(ParseError::new(digit_text, "a digit from 0 to 9"));
+            }
+
+            words.push((word.to_owned(), digit));
         }
+
+        Ok(Self { words })
     }
 }
 
-impl Solver for Day1Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day1/input.txt").unwrap();
+/// A digit of a calibration line, written as a digit or spelled out, at the given byte offset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub offset: usize,
+    pub text: &'a str,
+    pub digit: usize,
+}
 
-        let mut total_calibrations = 0;
-        for line in file.lines() {
-            let captures = PART_1_FIRST_DIGIT_CALIBRATION_RE.captures(line).unwrap();
-            let first_calibration = Self::parse_calibration(&captures);
-            let captures = PART_1_SECOND_DIGIT_CALIBRATION_RE.captures(line).unwrap();
-            let second_calibration = Self::parse_calibration(&captures);
-
-            // The first calibration is in the 10s position, hence the multiplication.
-            let curr_calibration = first_calibration * 10 + second_calibration;
-            total_calibrations += curr_calibration;
+/// Finds the first and last digits of a line, each in a single pass from its end of the line.
+/// As both ends are scanned separately, overlapping words like "oneight" are both found: "one"
+/// first, and "eight" last.
+pub struct Scanner {
+    vocabulary: Vocabulary,
+}
+
+impl Scanner {
+    /// A scanner of the digits written as digits, or spelled out with the words of the
+    /// vocabulary.
+    pub fn new(vocabulary: Vocabulary) -> Self {
+        Self { vocabulary }
+    }
+
+    // The token starting at the given offset of the line, if any, preferring the longest word if
+    // several of them match. Words are valid UTF-8, so they can only match from the start of a
+    // character.
+    fn token_at<'a>(&self, line: &'a str, offset: usize) -> Option<Token<'a>> {
+        let rest = &line.as_bytes()[offset..];
+        if rest[0].is_ascii_digit() {
+            return Some(Token {
+                offset,
+                text: &line[offset..offset + 1],
+                digit: (rest[0] - b'0') as usize,
+            });
         }
 
-        println!("Sum of all calibrations: {total_calibrations}.");
+        self.vocabulary
+            .words
+            .iter()
+            .filter(|(word, _)| rest.starts_with(word.as_bytes()))
+            .max_by_key(|(word, _)| word.len())
+            .map(|(word, digit)| Token {
+                offset,
+                text: &line[offset..offset + word.len()],
+                digit: *digit,
+            })
+    }
+
+    pub fn first<'a>(&self, line: &'a str) -> Option<Token<'a>> {
+        (0..line.len()).find_map(|offset| self.token_at(line, offset))
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day1/input.txt").unwrap();
+    pub fn last<'a>(&self, line: &'a str) -> Option<Token<'a>> {
+        (0..line.len())
+            .rev()
+            .find_map(|offset| self.token_at(line, offset))
+    }
+
+    /// Finds the first and last digits of the line, or `None` if it has no digit.
+    pub fn calibrate<'a>(&self, line: &'a str) -> Option<Calibration<'a>> {
+        Some(Calibration {
+            first: self.first(line)?,
+            last: self.last(line)?,
+        })
+    }
+}
+
+/// The first and last digits of a line, which are the same token if it has a single digit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Calibration<'a> {
+    pub first: Token<'a>,
+    pub last: Token<'a>,
+}
+
+impl Calibration<'_> {
+    pub fn value(&self) -> usize {
+        // The first digit is in the 10s position, hence the multiplication.
+        self.first.digit * 10 + self.last.digit
+    }
+}
+
+pub struct Day1Solver {}
+
+impl Day1Solver {
+    fn find_calibration(line: &str, scanner: &Scanner) -> ParseResult<usize> {
+        scanner
+            .calibrate(line)
+            .map(|calibration| calibration.value())
+            .ok_or_else(|| ParseError::new(line, "a line with a digit"))
+    }
 
+    /// Sums the calibration values of the lines, with the digits found by the scanner.
+    pub fn solve_problem(input: &str, scanner: &Scanner) -> ParseResult<usize> {
         let mut total_calibrations = 0;
-        for line in file.lines() {
-            let captures = PART_2_FIRST_DIGIT_CALIBRATION_RE.captures(line).unwrap();
-            let first_calibration = Self::parse_calibration(&captures);
-            let captures = PART_2_SECOND_DIGIT_CALIBRATION_RE.captures(line).unwrap();
-            let second_calibration = Self::parse_calibration(&captures);
-
-            // The first calibration is in the 10s position, hence the multiplication.
-            let curr_calibration = first_calibration * 10 + second_calibration;
-            total_calibrations += curr_calibration;
+        for line in input.lines() {
+            total_calibrations += Self::find_calibration(line, scanner)?;
         }
 
-        println!("Sum of all calibrations: {total_calibrations}.");
+        Ok(total_calibrations)
+    }
+}
+
+impl Solver for Day1Solver {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 1;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_problem(input, &Scanner::new(Vocabulary::default()))?.into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_problem(input, &Scanner::new(Vocabulary::english()))?.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        // Part 2's scanner also finds spelled-out digits, so it accepts every line part 1's does.
+        Self::solve_problem(input, &Scanner::new(Vocabulary::english()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day1Solver::solve_part1(include_str!("input-small1.txt"))
+                .unwrap()
+                .to_string(),
+            "142"
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day1Solver::solve_part2(include_str!("input-small2.txt"))
+                .unwrap()
+                .to_string(),
+            "281"
+        );
+    }
+
+    #[test]
+    fn examples_are_valid() {
+        assert!(Day1Solver::validate(include_str!("input-small1.txt")).is_ok());
+        assert!(Day1Solver::validate(include_str!("input-small2.txt")).is_ok());
+        assert!(Day1Solver::validate("xyz\n").is_err());
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let scanner = Scanner::new(Vocabulary::english());
+        let line = "xoneightx";
+
+        let first = scanner.first(line).unwrap();
+        assert_eq!((first.offset, first.text, first.digit), (1, "one", 1));
+        let last = scanner.last(line).unwrap();
+        assert_eq!((last.offset, last.text, last.digit), (3, "eight", 8));
+        assert_eq!(Day1Solver::find_calibration(line, &scanner).unwrap(), 18);
+    }
+
+    #[test]
+    fn line_without_digit() {
+        let scanner = Scanner::new(Vocabulary::english());
+
+        assert_eq!(scanner.calibrate("xyz"), None);
+        assert!(Day1Solver::find_calibration("xyz", &scanner).is_err());
+        assert_eq!(scanner.calibrate("x7y").unwrap().value(), 77);
+    }
+
+    #[test]
+    fn other_vocabularies() {
+        let scanner = Scanner::new(Vocabulary::built_in("german").unwrap());
+        assert_eq!(
+            Day1Solver::find_calibration("zweifünfx", &scanner).unwrap(),
+            25
+        );
+
+        let vocabulary = Vocabulary::parse("un = 1\nune = 1\n\nzéro = 0\n").unwrap();
+        let scanner = Scanner::new(vocabulary);
+        let first = scanner.first("unezéro").unwrap();
+        assert_eq!((first.text, first.digit), ("une", 1));
+        assert_eq!(
+            Day1Solver::find_calibration("unezéro", &scanner).unwrap(),
+            10
+        );
+
+        assert!(Vocabulary::parse("dix = 10").is_err());
+        assert!(Vocabulary::parse(" = 1").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "nightly"))]
+mod benches {
+    use super::*;
+    use regex::Regex;
+    use std::sync::LazyLock;
+    use test::Bencher;
+
+    static FIRST_DIGIT_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^.*?(\d|one|two|three|four|five|six|seven|eight|nine).*$").unwrap()
+    });
+    static LAST_DIGIT_RE: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^.*(\d|one|two|three|four|five|six|seven|eight|nine).*?$").unwrap()
+    });
+
+    // The regexes the scanner replaced, to compare against it.
+    fn solve_part2_with_regexes(input: &str) -> usize {
+        let digit = |text: &str| match ENGLISH.iter().position(|word| *word == text) {
+            Some(index) => index + 1,
+            None => text.parse().unwrap(),
+        };
+
+        input
+            .lines()
+            .map(|line| {
+                let first = &FIRST_DIGIT_RE.captures(line).unwrap()[1];
+                let last = &LAST_DIGIT_RE.captures(line).unwrap()[1];
+                digit(first) * 10 + digit(last)
+            })
+            .sum()
+    }
+
+    #[bench]
+    fn bench_part2_scanner(b: &mut Bencher) {
+        let file = crate::bench_input(1);
+        let scanner = Scanner::new(Vocabulary::english());
+
+        b.iter(|| Day1Solver::solve_problem(&file, &scanner).unwrap());
+    }
+
+    #[bench]
+    fn bench_part2_regexes(b: &mut Bencher) {
+        let file = crate::bench_input(1);
+
+        b.iter(|| solve_part2_with_regexes(&file));
     }
 }
@@ -1,13 +1,12 @@
 use std::str::Lines;
 
-use crate::solver::Solver;
-
-const TILE_GRID_SIZE: usize = 140;
-// Conversion from tile grid to space grid is 2x + 1.
-const SPACE_GRID_SIZE: usize = 281;
+use crate::geometry::{Direction, Position};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Pipe {
+pub enum Pipe {
     Vertical,
     Horizontal,
     NorthAndEast,
@@ -18,9 +17,9 @@ enum Pipe {
     Start,
 }
 
-impl From<char> for Pipe {
-    fn from(value: char) -> Self {
-        match value {
+impl Pipe {
+    pub fn from_char(value: char) -> Option<Self> {
+        let pipe = match value {
             '|' => Pipe::Vertical,
             '-' => Pipe::Horizontal,
             'L' => Pipe::NorthAndEast,
@@ -29,29 +28,32 @@ impl From<char> for Pipe {
             'F' => Pipe::SouthAndEast,
             '.' => Pipe::Ground,
             'S' => Pipe::Start,
-            _ => unreachable!(),
-        }
+            _ => return None,
+        };
+
+        Some(pipe)
+    }
+
+    /// Returns the two directions the pipe connects to, or `None` if it isn't a pipe.
+    pub fn directions(self) -> Option<(Direction, Direction)> {
+        let directions = match self {
+            Pipe::Vertical => (Direction::Up, Direction::Down),
+            Pipe::Horizontal => (Direction::Left, Direction::Right),
+            Pipe::NorthAndEast => (Direction::Up, Direction::Right),
+            Pipe::NorthAndWest => (Direction::Up, Direction::Left),
+            Pipe::SouthAndWest => (Direction::Down, Direction::Left),
+            Pipe::SouthAndEast => (Direction::Down, Direction::Right),
+            Pipe::Ground | Pipe::Start => return None,
+        };
+
+        Some(directions)
     }
 }
 
 // The top-left point of the tile grid is set as the origin (0,0). Going down increments the row
 // (e.g. (1,0), (2,0), etc.), while going to the right increments the column
 // (e.g. (0,1), (0,2), etc.).
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct TilePosition {
-    row: usize,
-    col: usize,
-}
-
-impl TilePosition {
-    fn new(row: usize, col: usize) -> Self {
-        if row >= TILE_GRID_SIZE || col >= TILE_GRID_SIZE {
-            unreachable!()
-        } else {
-            Self { row, col }
-        }
-    }
-}
+pub type TilePosition = Position;
 
 macro_rules! tile_pos {
     ($row:expr, $col:expr) => {
@@ -77,153 +79,91 @@ impl Tile {
     // Given a "from" position (which should be adjacential to the current tile),
     // returns the next tile we would connect to if we went from "from" to the current tile.
     // There can be at most one valid next position, but sometimes there is none if the
-    // "from" position cannot legally connect to the current tile.
+    // "from" position cannot legally connect to the current tile. The tile doesn't know the size
+    // of its grid, so the next position may be beyond the last row or column.
     //
     // Important: do not call if the tile is a start tile, as the start tile doesn't know
     // where it actually connects. If you do so, this will panic. First call `is_start_tile`
     // before this to verify.
     fn try_connect_to_next_tile_from(&self, from: TilePosition) -> Option<TilePosition> {
-        match self.pipe {
-            Pipe::Vertical => {
-                if self.pos.row == 0
-                    || self.pos.row == TILE_GRID_SIZE - 1
-                    || self.pos.col != from.col
-                    || (self.pos.row - 1 != from.row && self.pos.row + 1 != from.row)
-                {
-                    None
-                } else if self.pos.row + 1 == from.row {
-                    Some(tile_pos! { self.pos.row - 1, self.pos.col })
-                } else {
-                    Some(tile_pos! { self.pos.row + 1, self.pos.col })
-                }
-            }
-            Pipe::Horizontal => {
-                if self.pos.col == 0
-                    || self.pos.col == TILE_GRID_SIZE - 1
-                    || self.pos.row != from.row
-                    || (self.pos.col - 1 != from.col && self.pos.col + 1 != from.col)
-                {
-                    None
-                } else if self.pos.col + 1 == from.col {
-                    Some(tile_pos! { self.pos.row, self.pos.col - 1 })
-                } else {
-                    Some(tile_pos! { self.pos.row, self.pos.col + 1 })
-                }
-            }
-            Pipe::NorthAndEast => {
-                if self.pos.row == 0 || self.pos.col == TILE_GRID_SIZE - 1 {
-                    None
-                } else if self.pos.row - 1 == from.row && self.pos.col == from.col {
-                    Some(tile_pos! { self.pos.row, self.pos.col + 1 })
-                } else if self.pos.row == from.row && self.pos.col + 1 == from.col {
-                    Some(tile_pos! { self.pos.row - 1, self.pos.col})
-                } else {
-                    None
-                }
-            }
-            Pipe::NorthAndWest => {
-                if self.pos.row == 0 || self.pos.col == 0 {
-                    None
-                } else if self.pos.row - 1 == from.row && self.pos.col == from.col {
-                    Some(tile_pos! { self.pos.row, self.pos.col - 1 })
-                } else if self.pos.row == from.row && self.pos.col - 1 == from.col {
-                    Some(tile_pos! { self.pos.row - 1, self.pos.col})
-                } else {
-                    None
-                }
-            }
-            Pipe::SouthAndWest => {
-                if self.pos.row == TILE_GRID_SIZE - 1 || self.pos.col == 0 {
-                    None
-                } else if self.pos.row + 1 == from.row && self.pos.col == from.col {
-                    Some(tile_pos! { self.pos.row, self.pos.col - 1 })
-                } else if self.pos.row == from.row && self.pos.col - 1 == from.col {
-                    Some(tile_pos! { self.pos.row + 1, self.pos.col})
-                } else {
-                    None
-                }
-            }
-            Pipe::SouthAndEast => {
-                if self.pos.row == TILE_GRID_SIZE - 1 || self.pos.col == TILE_GRID_SIZE - 1 {
-                    None
-                } else if self.pos.row + 1 == from.row && self.pos.col == from.col {
-                    Some(tile_pos! { self.pos.row, self.pos.col + 1 })
-                } else if self.pos.row == from.row && self.pos.col + 1 == from.col {
-                    Some(tile_pos! { self.pos.row + 1, self.pos.col})
-                } else {
-                    None
-                }
-            }
-            // Ground connects to nothing.
-            Pipe::Ground => None,
-            Pipe::Start => panic!(
+        if self.is_start_tile() {
+            panic!(
                 "A Start tile doesn't know where it connects to, so calling this function panics."
-            ),
+            );
+        }
+
+        // Ground connects to nothing.
+        let (first, second) = self.pipe.directions()?;
+        if self.pos.step(first) == Some(from) {
+            self.pos.step(second)
+        } else if self.pos.step(second) == Some(from) {
+            self.pos.step(first)
+        } else {
+            None
         }
     }
 }
 
 #[derive(Debug)]
-struct TileGrid {
-    tiles: Vec<Vec<Tile>>,
+pub struct TileGrid {
+    tiles: Grid<Tile>,
     start_pos: TilePosition,
 }
 
 impl TileGrid {
-    fn new(lines: Lines) -> Self {
+    pub fn new(lines: Lines) -> ParseResult<Self> {
         let mut start_pos = None;
-        let tiles = lines
-            .enumerate()
-            .map(|(row, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(col, character)| {
-                        let pipe = character.into();
-                        let pos = tile_pos! {row, col};
-                        if pipe == Pipe::Start {
-                            start_pos = Some(pos);
-                        }
-
-                        Tile::new(pipe, pos)
-                    })
-                    .collect()
-            })
-            .collect();
+        let mut last_line = "";
+        let tiles = Grid::parse_rows(lines, |row, line| {
+            last_line = line;
+            parse::chars(line, "a pipe", Pipe::from_char)
+                .enumerate()
+                .map(|(col, pipe)| {
+                    let pipe = pipe?;
+                    let pos = tile_pos! {row, col};
+                    if pipe == Pipe::Start {
+                        start_pos = Some(pos);
+                    }
 
-        let start_pos = start_pos.unwrap();
-        TileGrid { tiles, start_pos }
+                    Ok(Tile::new(pipe, pos))
+                })
+                .collect()
+        })?;
+
+        let start_pos = start_pos
+            .ok_or_else(|| ParseError::new(&last_line[last_line.len()..], "a start tile"))?;
+        Ok(TileGrid { tiles, start_pos })
     }
 
-    fn get_tile(&self, pos: TilePosition) -> &Tile {
-        &self.tiles[pos.row][pos.col]
+    // Returns the tile at the position, or `None` if it is beyond the grid.
+    fn get_tile(&self, pos: TilePosition) -> Option<&Tile> {
+        self.tiles.get(pos)
     }
 
-    fn explore_loop(&self) -> Vec<TilePosition> {
-        let TilePosition {
-            row: start_row,
-            col: start_col,
-        } = self.start_pos;
-        // This is safe as long as the start of the loop is not on the
-        // first nor last row/col.
-        let explore_paths = [
-            tile_pos! { start_row - 1, start_col},
-            tile_pos! { start_row, start_col + 1 },
-            tile_pos! { start_row + 1, start_col },
-            tile_pos! { start_row, start_col - 1},
-        ];
+    // Like `Tile::try_connect_to_next_tile_from`, but only connects to tiles within the grid.
+    fn try_connect_to_next_tile_from(
+        &self,
+        pos: TilePosition,
+        from: TilePosition,
+    ) -> Option<TilePosition> {
+        let next_pos = self.get_tile(pos)?.try_connect_to_next_tile_from(from)?;
+        self.get_tile(next_pos).map(|_| next_pos)
+    }
 
-        for mut next_pos in explore_paths {
+    /// Returns the positions of the loop's tiles, starting and ending at the start tile, or `None`
+    /// if the start tile isn't on a loop.
+    pub fn explore_loop(&self) -> Option<Vec<TilePosition>> {
+        for mut next_pos in self.tiles.neighbours4(self.start_pos) {
             let mut curr_pos = self.start_pos;
             let mut visited_loop = vec![curr_pos];
 
-            loop {
-                let next_tile = self.get_tile(next_pos);
+            while let Some(next_tile) = self.get_tile(next_pos) {
                 if next_tile.is_start_tile() {
                     visited_loop.push(next_pos);
-                    return visited_loop;
+                    return Some(visited_loop);
                 }
 
-                match next_tile.try_connect_to_next_tile_from(curr_pos) {
+                match self.try_connect_to_next_tile_from(next_pos, curr_pos) {
                     Some(connected_pos) => {
                         visited_loop.push(next_pos);
                         curr_pos = next_pos;
@@ -234,7 +174,7 @@ impl TileGrid {
             }
         }
 
-        unreachable!()
+        None
     }
 }
 
@@ -244,41 +184,7 @@ impl TileGrid {
 // can either be a tile location, or between two tiles. All even rows and columns are between-tile
 // spaces, whereas all positions which have both odd rows and columns are tile locations. Hence,
 // why converting from a `TilePosition` to a `SpacePosition` follows a 2x+1 function.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-struct SpacePosition {
-    row: usize,
-    col: usize,
-}
-
-impl From<TilePosition> for SpacePosition {
-    fn from(value: TilePosition) -> Self {
-        let TilePosition { row, col } = value;
-        Self {
-            row: 2 * row + 1,
-            col: 2 * col + 1,
-        }
-    }
-}
-
-impl SpacePosition {
-    fn new(row: usize, col: usize) -> Self {
-        if row >= SPACE_GRID_SIZE || col >= SPACE_GRID_SIZE {
-            unreachable!()
-        } else {
-            Self { row, col }
-        }
-    }
-
-    // Returns the average position in terms of rows and columns.
-    // In practice, this is used to find the non-tile position between two
-    // contiguous tile positions.
-    fn space_position_between(&self, other: SpacePosition) -> SpacePosition {
-        let row = (self.row + other.row) / 2;
-        let col = (self.col + other.col) / 2;
-
-        Self { row, col }
-    }
-}
+type SpacePosition = Position;
 
 macro_rules! space_pos {
     ($row:expr, $col:expr) => {
@@ -286,6 +192,18 @@ macro_rules! space_pos {
     };
 }
 
+fn to_space_position(tile_position: TilePosition) -> SpacePosition {
+    let TilePosition { row, col } = tile_position;
+    space_pos! { 2 * row + 1, 2 * col + 1 }
+}
+
+// Returns the average position in terms of rows and columns.
+// In practice, this is used to find the non-tile position between two
+// contiguous tile positions.
+fn space_position_between(first: SpacePosition, second: SpacePosition) -> SpacePosition {
+    space_pos! { (first.row + second.row) / 2, (first.col + second.col) / 2 }
+}
+
 #[derive(Copy, Clone, PartialEq)]
 enum SpaceState {
     LoopTile,
@@ -305,10 +223,7 @@ impl SpaceState {
     }
 
     fn is_unvisited_non_loop_tile(&self) -> bool {
-        match self {
-            Self::NonLoopTile { visited } if !*visited => true,
-            _ => false,
-        }
+        matches!(self, Self::NonLoopTile { visited } if !*visited)
     }
 }
 
@@ -318,41 +233,39 @@ struct Space {
 }
 
 struct SpaceGrid {
-    spaces: Vec<Vec<Space>>,
-}
-
-impl Default for SpaceGrid {
-    fn default() -> Self {
-        Self {
-            spaces: vec![vec![Space::default(); SPACE_GRID_SIZE]; SPACE_GRID_SIZE],
-        }
-    }
+    spaces: Grid<Space>,
 }
 
 impl SpaceGrid {
-    fn new(explored_loop: &Vec<TilePosition>) -> Self {
-        let mut grid = SpaceGrid::default();
+    fn new(tile_grid: &TileGrid, explored_loop: &[TilePosition]) -> Self {
+        // Conversion from tile grid to space grid is 2x + 1.
+        let num_rows = 2 * tile_grid.tiles.num_rows() + 1;
+        let num_cols = 2 * tile_grid.tiles.num_cols() + 1;
+        let mut grid = SpaceGrid {
+            spaces: Grid::filled(num_rows, num_cols, Space::default()),
+        };
         for two_contiguous_loop_tile_positions in explored_loop.windows(2) {
-            let first_contiguous_loop_space_position = two_contiguous_loop_tile_positions[0].into();
+            let first_contiguous_loop_space_position =
+                to_space_position(two_contiguous_loop_tile_positions[0]);
             let second_contiguous_loop_space_position =
-                two_contiguous_loop_tile_positions[1].into();
+                to_space_position(two_contiguous_loop_tile_positions[1]);
 
             // Marks the tile and the space between this tile and the next tile as being
             // spaces covered by the loop. The latter is subtler yet crucial to later on
             // explore the space that squeezes in between pipes that are part of the loop.
             grid.get_mut_space(first_contiguous_loop_space_position)
                 .state = SpaceState::LoopTile;
-            grid.get_mut_space(
-                first_contiguous_loop_space_position
-                    .space_position_between(second_contiguous_loop_space_position),
-            )
+            grid.get_mut_space(space_position_between(
+                first_contiguous_loop_space_position,
+                second_contiguous_loop_space_position,
+            ))
             .state = SpaceState::LoopTile;
         }
 
         // We step through the grid by steps of 2 and starting at row/col 1 to iterate through
         // actual tiles, i.e. strictly odd rows/columns.
-        for i in (1..SPACE_GRID_SIZE).step_by(2) {
-            for j in (1..SPACE_GRID_SIZE).step_by(2) {
+        for i in (1..num_rows).step_by(2) {
+            for j in (1..num_cols).step_by(2) {
                 let space = grid.get_mut_space(space_pos! { i, j });
                 // Ignore tiles that were previously tagged as being part of the loop.
                 if !space.state.is_part_of_loop() {
@@ -388,60 +301,57 @@ impl SpaceGrid {
                 }
             }
 
-            let SpacePosition { row, col } = space_position;
-            if row > 0 {
-                space_positions_to_visit.push(space_pos! { row-1, col });
-            }
-            if col < SPACE_GRID_SIZE - 1 {
-                space_positions_to_visit.push(space_pos! { row, col+1 });
-            }
-            if row < SPACE_GRID_SIZE - 1 {
-                space_positions_to_visit.push(space_pos! { row+1, col });
-            }
-            if col > 0 {
-                space_positions_to_visit.push(space_pos! { row, col-1 });
-            }
+            space_positions_to_visit.extend(self.spaces.neighbours4(space_position));
         }
     }
 
     fn get_mut_space(&mut self, pos: SpacePosition) -> &mut Space {
-        &mut self.spaces[pos.row][pos.col]
+        &mut self.spaces[pos]
     }
 }
 
+// Parses the tile grid and explores its loop, which must go through the start tile.
+fn parse_loop(input: &str) -> ParseResult<(TileGrid, Vec<TilePosition>)> {
+    let tile_grid = TileGrid::new(input.lines())?;
+    let explored_loop = tile_grid.explore_loop().ok_or_else(|| {
+        // The grid was parsed, so the input has a start tile.
+        let start = input.find('S').unwrap();
+        ParseError::new(&input[start..start + 1], "a start tile on a loop")
+    })?;
+
+    Ok((tile_grid, explored_loop))
+}
+
 pub struct Day10Solver {}
 
 impl Solver for Day10Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day10/input.txt").unwrap();
-        let grid = TileGrid::new(file.lines());
-        let explored_loop = grid.explore_loop();
-        println!(
-            "The farthest point along the loop from the starting position is {}",
-            explored_loop.len() / 2
-        );
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 10;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let (_, explored_loop) = parse_loop(input)?;
+
+        Ok((explored_loop.len() / 2).into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day10/input.txt").unwrap();
-        let tile_grid = TileGrid::new(file.lines());
-        let explored_loop = tile_grid.explore_loop();
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let (tile_grid, explored_loop) = parse_loop(input)?;
 
-        let mut space_grid = SpaceGrid::new(&explored_loop);
+        let mut space_grid = SpaceGrid::new(&tile_grid, &explored_loop);
         space_grid.visit_all_spaces_outside_of_the_loop();
 
         let num_enclosed_tiles: usize = space_grid
             .spaces
             .iter()
-            .map(|row_of_spaces| {
-                row_of_spaces
-                    .iter()
-                    .filter(|space| space.state.is_unvisited_non_loop_tile())
-                    .count()
-            })
-            .sum();
+            .filter(|(_, space)| space.state.is_unvisited_non_loop_tile())
+            .count();
 
-        println!("The number of tiles enclosed by the loop is {num_enclosed_tiles}");
+        Ok(num_enclosed_tiles.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        parse_loop(input)?;
+        Ok(())
     }
 }
 
@@ -450,20 +360,53 @@ mod tests {
     use super::*;
 
     #[test]
-    #[should_panic]
-    fn position_beyond_row_bound_panics() {
-        TilePosition::new(TILE_GRID_SIZE, 0);
+    fn part1_example() {
+        assert_eq!(
+            Day10Solver::solve_part1(include_str!("input-small1.txt"))
+                .unwrap()
+                .to_string(),
+            "4"
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn position_beyond_column_bound_panics() {
-        TilePosition::new(0, TILE_GRID_SIZE);
+    fn part2_example() {
+        assert_eq!(
+            Day10Solver::solve_part2(include_str!("input-small2.txt"))
+                .unwrap()
+                .to_string(),
+            "8"
+        );
+    }
+
+    const GRID_SIZE: usize = 5;
+
+    // A grid of ground, except for the tile.
+    fn grid_with_tile(tile: Tile) -> TileGrid {
+        let tiles = (0..GRID_SIZE)
+            .flat_map(|row| (0..GRID_SIZE).map(move |col| tile_pos! { row, col }))
+            .map(|pos| Tile::new(Pipe::Ground, pos))
+            .collect();
+        let mut tiles = Grid::new(GRID_SIZE, GRID_SIZE, tiles);
+        let pos = tile.pos;
+        tiles[pos] = tile;
+
+        TileGrid {
+            tiles,
+            start_pos: pos,
+        }
+    }
+
+    #[test]
+    fn position_beyond_the_grid_has_no_tile() {
+        let grid = grid_with_tile(Tile::new(Pipe::Vertical, tile_pos! { 0, 0 }));
+        assert!(grid.get_tile(tile_pos! { GRID_SIZE, 0 }).is_none());
+        assert!(grid.get_tile(tile_pos! { 0, GRID_SIZE }).is_none());
     }
 
     #[test]
     fn vertical_tile_at_origin_never_connects() {
-        let tile = Tile::new('|'.into(), tile_pos! { 0, 0 });
+        let tile = Tile::new(Pipe::Vertical, tile_pos! { 0, 0 });
         assert!(tile
             .try_connect_to_next_tile_from(tile_pos! {0, 0})
             .is_none());
@@ -486,27 +429,28 @@ mod tests {
 
     #[test]
     fn vertical_tile_at_last_row_never_connects() {
-        let tile = Tile::new('|'.into(), tile_pos! { TILE_GRID_SIZE-1, 1 });
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 1})
+        let pos = tile_pos! { GRID_SIZE-1, 1 };
+        let grid = grid_with_tile(Tile::new(Pipe::Vertical, pos));
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-2, 1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-2, 1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-3, 1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-3, 1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 0})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 0})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 2})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 2})
             .is_none());
     }
 
     #[test]
     fn vertical_tile_in_valid_position_connects_sometimes() {
-        let tile = Tile::new('|'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::Vertical, tile_pos! { 2, 2 });
         // Two valid connection positions.
         assert_eq!(
             tile.try_connect_to_next_tile_from(tile_pos! {1, 2}),
@@ -537,7 +481,7 @@ mod tests {
 
     #[test]
     fn horizontal_tile_at_origin_never_connects() {
-        let tile = Tile::new('-'.into(), tile_pos! { 0, 0 });
+        let tile = Tile::new(Pipe::Horizontal, tile_pos! { 0, 0 });
         assert!(tile
             .try_connect_to_next_tile_from(tile_pos! {0, 0})
             .is_none());
@@ -560,27 +504,28 @@ mod tests {
 
     #[test]
     fn horizontal_tile_at_last_col_never_connects() {
-        let tile = Tile::new('-'.into(), tile_pos! { 1, TILE_GRID_SIZE-1 });
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {1, TILE_GRID_SIZE-1})
+        let pos = tile_pos! { 1, GRID_SIZE-1 };
+        let grid = grid_with_tile(Tile::new(Pipe::Horizontal, pos));
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {1, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {1, TILE_GRID_SIZE-2})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {1, GRID_SIZE-2})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {1, TILE_GRID_SIZE-3})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {1, GRID_SIZE-3})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {0, TILE_GRID_SIZE-1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {0, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {2, TILE_GRID_SIZE-1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {2, GRID_SIZE-1})
             .is_none());
     }
 
     #[test]
     fn horizontal_tile_in_valid_position_connects_sometimes() {
-        let tile = Tile::new('-'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::Horizontal, tile_pos! { 2, 2 });
         // Two valid connection positions.
         assert_eq!(
             tile.try_connect_to_next_tile_from(tile_pos! {2, 1}),
@@ -611,7 +556,7 @@ mod tests {
 
     #[test]
     fn north_east_tile_at_first_row_never_connects() {
-        let tile = Tile::new('L'.into(), tile_pos! { 0, 1 });
+        let tile = Tile::new(Pipe::NorthAndEast, tile_pos! { 0, 1 });
         assert!(tile
             .try_connect_to_next_tile_from(tile_pos! {0, 0})
             .is_none());
@@ -634,27 +579,28 @@ mod tests {
 
     #[test]
     fn north_east_tile_at_last_col_never_connects() {
-        let tile = Tile::new('L'.into(), tile_pos! { 1, TILE_GRID_SIZE-1 });
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {1, TILE_GRID_SIZE-1})
+        let pos = tile_pos! { 1, GRID_SIZE-1 };
+        let grid = grid_with_tile(Tile::new(Pipe::NorthAndEast, pos));
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {1, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {1, TILE_GRID_SIZE-2})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {1, GRID_SIZE-2})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {1, TILE_GRID_SIZE-3})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {1, GRID_SIZE-3})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {0, TILE_GRID_SIZE-1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {0, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {2, TILE_GRID_SIZE-1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {2, GRID_SIZE-1})
             .is_none());
     }
 
     #[test]
     fn north_east_tile_in_valid_position_connects_sometimes() {
-        let tile = Tile::new('L'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::NorthAndEast, tile_pos! { 2, 2 });
         // Two valid connection positions.
         assert_eq!(
             tile.try_connect_to_next_tile_from(tile_pos! {1, 2}),
@@ -685,7 +631,7 @@ mod tests {
 
     #[test]
     fn north_west_tile_at_first_row_never_connects() {
-        let tile = Tile::new('J'.into(), tile_pos! { 0, 1 });
+        let tile = Tile::new(Pipe::NorthAndWest, tile_pos! { 0, 1 });
         assert!(tile
             .try_connect_to_next_tile_from(tile_pos! {0, 0})
             .is_none());
@@ -708,7 +654,7 @@ mod tests {
 
     #[test]
     fn north_west_tile_at_first_col_never_connects() {
-        let tile = Tile::new('J'.into(), tile_pos! { 2, 0 });
+        let tile = Tile::new(Pipe::NorthAndWest, tile_pos! { 2, 0 });
         assert!(tile
             .try_connect_to_next_tile_from(tile_pos! {0, 0})
             .is_none());
@@ -728,7 +674,7 @@ mod tests {
 
     #[test]
     fn north_west_tile_in_valid_position_connects_sometimes() {
-        let tile = Tile::new('J'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::NorthAndWest, tile_pos! { 2, 2 });
         // Two valid connection positions.
         assert_eq!(
             tile.try_connect_to_next_tile_from(tile_pos! {1, 2}),
@@ -759,30 +705,31 @@ mod tests {
 
     #[test]
     fn south_west_tile_at_last_row_never_connects() {
-        let tile = Tile::new('7'.into(), tile_pos! { TILE_GRID_SIZE-1, 2 });
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE - 2, 2})
+        let pos = tile_pos! { GRID_SIZE-1, 2 };
+        let grid = grid_with_tile(Tile::new(Pipe::SouthAndWest, pos));
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE - 2, 2})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 0})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 0})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 2})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 2})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 3})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 3})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-2, 1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-2, 1})
             .is_none());
     }
 
     #[test]
     fn south_west_tile_at_first_col_never_connects() {
-        let tile = Tile::new('7'.into(), tile_pos! { 2, 0 });
+        let tile = Tile::new(Pipe::SouthAndWest, tile_pos! { 2, 0 });
         assert!(tile
             .try_connect_to_next_tile_from(tile_pos! {1, 0})
             .is_none());
@@ -802,7 +749,7 @@ mod tests {
 
     #[test]
     fn south_west_tile_in_valid_position_connects_sometimes() {
-        let tile = Tile::new('7'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::SouthAndWest, tile_pos! { 2, 2 });
         // Two valid connection positions.
         assert_eq!(
             tile.try_connect_to_next_tile_from(tile_pos! {2, 1}),
@@ -833,50 +780,52 @@ mod tests {
 
     #[test]
     fn south_east_tile_at_last_row_never_connects() {
-        let tile = Tile::new('F'.into(), tile_pos! { TILE_GRID_SIZE-1, 2 });
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE - 2, 2})
+        let pos = tile_pos! { GRID_SIZE-1, 2 };
+        let grid = grid_with_tile(Tile::new(Pipe::SouthAndEast, pos));
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE - 2, 2})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 0})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 0})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 2})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 2})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-1, 3})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-1, 3})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {TILE_GRID_SIZE-2, 1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {GRID_SIZE-2, 1})
             .is_none());
     }
 
     #[test]
     fn south_east_tile_at_last_col_never_connects() {
-        let tile = Tile::new('F'.into(), tile_pos! { 2, TILE_GRID_SIZE-1 });
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {1, TILE_GRID_SIZE-1})
+        let pos = tile_pos! { 2, GRID_SIZE-1 };
+        let grid = grid_with_tile(Tile::new(Pipe::SouthAndEast, pos));
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {1, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {2, TILE_GRID_SIZE-1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {2, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {3, TILE_GRID_SIZE-1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {3, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {4, TILE_GRID_SIZE-1})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {4, GRID_SIZE-1})
             .is_none());
-        assert!(tile
-            .try_connect_to_next_tile_from(tile_pos! {2, TILE_GRID_SIZE-2})
+        assert!(grid
+            .try_connect_to_next_tile_from(pos, tile_pos! {2, GRID_SIZE-2})
             .is_none());
     }
 
     #[test]
     fn south_east_tile_in_valid_position_connects_sometimes() {
-        let tile = Tile::new('F'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::SouthAndEast, tile_pos! { 2, 2 });
         // Two valid connection positions.
         assert_eq!(
             tile.try_connect_to_next_tile_from(tile_pos! {2, 3}),
@@ -907,10 +856,10 @@ mod tests {
 
     #[test]
     fn ground_tile_never_connects() {
-        let tile = Tile::new('.'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::Ground, tile_pos! { 2, 2 });
 
-        for i in 0..TILE_GRID_SIZE {
-            for j in 0..TILE_GRID_SIZE {
+        for i in 0..GRID_SIZE {
+            for j in 0..GRID_SIZE {
                 assert!(tile
                     .try_connect_to_next_tile_from(tile_pos! {i, j})
                     .is_none());
@@ -921,7 +870,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn start_tile_connection_panics() {
-        let tile = Tile::new('S'.into(), tile_pos! { 2, 2 });
+        let tile = Tile::new(Pipe::Start, tile_pos! { 2, 2 });
         tile.try_connect_to_next_tile_from(tile_pos! {2, 2});
     }
 }
@@ -1,6 +1,5 @@
-use crate::solver::Solver;
-
-const IMAGE_SIZE: usize = 140;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 #[derive(Debug)]
 struct Galaxy {
@@ -11,24 +10,49 @@ struct Galaxy {
 pub struct Day11Solver {}
 
 impl Day11Solver {
-    fn find_sum_of_distances_between_expanded_galaxies(expansion_factor: usize) -> usize {
-        let file = std::fs::read_to_string("src/day11/input.txt").unwrap();
-
-        let mut empty_row = [true; IMAGE_SIZE];
-        let mut empty_col = [true; IMAGE_SIZE];
+    // Returns the positions of the galaxies in the image, which must have one.
+    fn parse_galaxies(input: &str) -> ParseResult<Vec<Galaxy>> {
         let mut galaxies = Vec::new();
-        for (row, line) in file.lines().enumerate() {
-            for (col, character) in line.chars().enumerate() {
-                if character != '#' {
-                    continue;
+        for (row, line) in input.lines().enumerate() {
+            let is_galaxy = |character| match character {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            };
+            for (col, is_galaxy) in parse::chars(line, "`#` or `.`", is_galaxy).enumerate() {
+                if is_galaxy? {
+                    galaxies.push(Galaxy { row, col });
                 }
-
-                empty_row[row] = false;
-                empty_col[col] = false;
-                galaxies.push(Galaxy { row, col });
             }
         }
 
+        if galaxies.is_empty() {
+            return Err(ParseError::new(&input[input.len()..], "a galaxy"));
+        }
+
+        Ok(galaxies)
+    }
+
+    /// Sums the distances between all pairs of galaxies, once empty rows and columns are
+    /// expanded `expansion_factor` times.
+    pub fn find_sum_of_distances_between_expanded_galaxies(
+        input: &str,
+        expansion_factor: usize,
+    ) -> ParseResult<usize> {
+        let num_rows = input.lines().count();
+        let num_cols = input
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut galaxies = Self::parse_galaxies(input)?;
+        let mut empty_row = vec![true; num_rows];
+        let mut empty_col = vec![true; num_cols];
+        for galaxy in &galaxies {
+            empty_row[galaxy.row] = false;
+            empty_col[galaxy.col] = false;
+        }
+
         let rows_to_expand: Vec<_> = empty_row
             .iter()
             .enumerate()
@@ -66,16 +90,16 @@ impl Day11Solver {
         }
 
         let mut sum_of_lengths = 0;
-        for i in 0..(galaxies.len() - 1) {
-            for j in (i + 1)..galaxies.len() {
+        for (i, first_galaxy) in galaxies.iter().enumerate() {
+            for second_galaxy in &galaxies[i + 1..] {
                 let Galaxy {
                     row: first_galaxy_row,
                     col: first_galaxy_col,
-                } = galaxies[i];
+                } = *first_galaxy;
                 let Galaxy {
                     row: second_galaxy_row,
                     col: second_galaxy_col,
-                } = galaxies[j];
+                } = *second_galaxy;
 
                 let manhattan_distance = first_galaxy_row.abs_diff(second_galaxy_row)
                     + first_galaxy_col.abs_diff(second_galaxy_col);
@@ -83,22 +107,52 @@ impl Day11Solver {
             }
         }
 
-        sum_of_lengths
+        Ok(sum_of_lengths)
     }
 }
 
 impl Solver for Day11Solver {
-    fn solve_part1() {
-        println!(
-            "The sum of the lengths between all pairs of galaxies including expansion is {}",
-            Self::find_sum_of_distances_between_expanded_galaxies(2)
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 11;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        Ok(Self::find_sum_of_distances_between_expanded_galaxies(input, 2)?.into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        Ok(Self::find_sum_of_distances_between_expanded_galaxies(input, 1_000_000)?.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Self::parse_galaxies(input)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day11Solver::solve_part1(EXAMPLE).unwrap().to_string(),
+            "374"
         );
     }
 
-    fn solve_part2() {
-        println!(
-            "The sum of the lengths between all pairs of galaxies including expansion is {}",
-            Self::find_sum_of_distances_between_expanded_galaxies(1_000_000)
+    #[test]
+    fn part2_example() {
+        // The statement only gives the answers for smaller expansions.
+        assert_eq!(
+            Day11Solver::find_sum_of_distances_between_expanded_galaxies(EXAMPLE, 10).unwrap(),
+            1030
+        );
+        assert_eq!(
+            Day11Solver::find_sum_of_distances_between_expanded_galaxies(EXAMPLE, 100).unwrap(),
+            8410
         );
     }
 }
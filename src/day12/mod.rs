@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use std::str::Lines;
 
-use crate::solver::Solver;
+use crate::parse::{self, ParseResult};
+use crate::solver::{Answer, Solver};
 
 pub struct Day12Solver {}
 
 impl Day12Solver {
-    fn count_arrangements<'a>(
+    /// Counts the arrangements of the unknown record that match the goal record.
+    pub fn count_arrangements<'a>(
         unknown_record: &'a [char],
         goal_record: &'a [usize],
         cached_count_arrangements: &mut HashMap<(&'a [char], &'a [usize]), u64>,
@@ -70,17 +72,31 @@ impl Day12Solver {
         count_arrangements
     }
 
-    fn solve_problem(lines: Lines, folds: usize) -> u64 {
-        let mut sum_of_arrangements = 0;
+    // Returns the unknown and goal records of each line, still folded.
+    fn parse_records(lines: Lines) -> ParseResult<Vec<(Vec<char>, Vec<usize>)>> {
         let mut all_unknown_and_goal_records = Vec::new();
         for line in lines {
-            let (unknown_record, goal_record) = line.split_once(' ').unwrap();
-            let mut unknown_record: Vec<_> = unknown_record.chars().collect();
-            let mut goal_record: Vec<_> = goal_record
+            let (unknown_record, goal_record) = parse::split_once(line, " ")?;
+            let unknown_record: Vec<_> = parse::chars(unknown_record, "a spring", |spring| {
+                matches!(spring, '.' | '#' | '?').then_some(spring)
+            })
+            .collect::<ParseResult<_>>()?;
+            let goal_record: Vec<usize> = goal_record
                 .split(',')
-                .map(str::parse)
-                .map(Result::unwrap)
-                .collect();
+                .map(parse::number)
+                .collect::<ParseResult<_>>()?;
+
+            all_unknown_and_goal_records.push((unknown_record, goal_record));
+        }
+
+        Ok(all_unknown_and_goal_records)
+    }
+
+    /// Sums the arrangements of all records, each unfolded `folds` times.
+    pub fn solve_problem(lines: Lines, folds: usize) -> ParseResult<u64> {
+        let mut sum_of_arrangements = 0;
+        let mut all_unknown_and_goal_records = Self::parse_records(lines)?;
+        for (unknown_record, goal_record) in &mut all_unknown_and_goal_records {
             let unknown_record_clone = unknown_record.clone();
             let goal_record_clone = goal_record.clone();
             for _ in 0..folds {
@@ -88,48 +104,68 @@ impl Day12Solver {
                 unknown_record.extend(unknown_record_clone.clone());
                 goal_record.extend(goal_record_clone.clone());
             }
-
-            all_unknown_and_goal_records.push((unknown_record, goal_record));
         }
 
         for (unknown_record, goal_record) in &all_unknown_and_goal_records {
             let mut cached_count_arrangements = HashMap::new();
             cached_count_arrangements.reserve(2_000);
             sum_of_arrangements += Self::count_arrangements(
-                &unknown_record,
-                &goal_record,
+                unknown_record,
+                goal_record,
                 &mut cached_count_arrangements,
             );
         }
 
-        sum_of_arrangements
+        Ok(sum_of_arrangements)
     }
 }
 
 impl Solver for Day12Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day12/input.txt").unwrap();
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 12;
 
-        let sum_of_arrangements = Self::solve_problem(file.lines(), 0);
-        println!("The sum of all arrangements is {sum_of_arrangements}");
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_problem(input.lines(), 0)?.into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day12/input.txt").unwrap();
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_problem(input.lines(), 4)?.into())
+    }
 
-        let sum_of_arrangements = Self::solve_problem(file.lines(), 4);
-        println!("The sum of all arrangements is {sum_of_arrangements}");
+    fn parse(input: &str) -> ParseResult<()> {
+        Self::parse_records(input.lines())?;
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day12Solver::solve_part1(EXAMPLE).unwrap().to_string(), "21");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day12Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "525152"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "nightly"))]
+mod benches {
+    use super::*;
     use test::Bencher;
 
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        let file = std::fs::read_to_string("src/day12/input.txt").unwrap();
+        let file = crate::bench_input(12);
 
         b.iter(|| Day12Solver::solve_problem(file.lines(), 4));
     }
@@ -1,21 +1,25 @@
 use std::str::Lines;
 
-use crate::solver::Solver;
+use crate::geometry::Position;
+use crate::grid::Grid;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Orientation {
+pub enum Orientation {
     Horizontal,
     Vertical,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-struct ReflectionLine {
-    orientation: Orientation,
-    index: usize,
+pub struct ReflectionLine {
+    pub orientation: Orientation,
+    pub index: usize,
 }
 
 impl ReflectionLine {
-    fn value(&self) -> u64 {
+    /// The value of this reflection line when summarizing notes.
+    pub fn value(&self) -> u64 {
         match self.orientation {
             Orientation::Horizontal => 100 * self.index as u64,
             Orientation::Vertical => self.index as u64,
@@ -24,44 +28,44 @@ impl ReflectionLine {
 }
 
 #[derive(Debug)]
-struct Pattern {
-    pattern: Vec<Vec<char>>,
-    transposed_pattern: Vec<Vec<char>>,
+pub struct Pattern<'a> {
+    // The first line of the pattern in the input, to point at it when it is invalid.
+    first_line: &'a str,
+    pattern: Grid<char>,
+    transposed_pattern: Grid<char>,
+    reflection_line: ReflectionLine,
 }
 
-impl Pattern {
-    // This assumes a rectangular matrix.
-    fn new(pattern: Vec<Vec<char>>) -> Self {
-        let num_transposed_rows = pattern[0].len();
-        let num_transposed_cols = pattern.len();
-        let mut transposed_pattern =
-            vec![vec![char::default(); num_transposed_cols]; num_transposed_rows];
-
-        for i in 0..num_transposed_rows {
-            for j in 0..num_transposed_cols {
-                transposed_pattern[i][j] = pattern[j][i];
-            }
-        }
+impl<'a> Pattern<'a> {
+    /// Creates the pattern starting at the given line of the input, which must have a
+    /// reflection line.
+    pub fn new(first_line: &'a str, pattern: Grid<char>) -> ParseResult<Self> {
+        let transposed_pattern = pattern.transpose();
+        let reflection_line = Self::find_reflection_line(&pattern, &transposed_pattern, None)
+            .ok_or_else(|| ParseError::new(first_line, "a pattern with a reflection line"))?;
 
-        Self {
+        Ok(Self {
+            first_line,
             pattern,
             transposed_pattern,
-        }
+            reflection_line,
+        })
     }
 
-    fn flip_smudge(&mut self, row: usize, col: usize) {
-        let smudge = self.pattern[row][col];
+    fn flip_smudge(&mut self, position: Position) {
+        let transposed_position = Position::new(position.col, position.row);
+        let smudge = self.pattern[position];
         if smudge == '#' {
-            self.pattern[row][col] = '.';
-            self.transposed_pattern[col][row] = '.';
+            self.pattern[position] = '.';
+            self.transposed_pattern[transposed_position] = '.';
         } else {
-            self.pattern[row][col] = '#';
-            self.transposed_pattern[col][row] = '#';
+            self.pattern[position] = '#';
+            self.transposed_pattern[transposed_position] = '#';
         }
     }
 
-    fn find_reflections(pattern: &Vec<Vec<char>>) -> Vec<usize> {
-        let num_cols = pattern[0].len();
+    fn find_reflections(pattern: &Grid<char>) -> Vec<usize> {
+        let num_cols = pattern.num_cols();
 
         let mut reflections = Vec::new();
         'reflection: for reflection in 1..num_cols {
@@ -69,7 +73,7 @@ impl Pattern {
             let right_reflection_size = num_cols - left_reflection_size;
             let min_reflection_size = left_reflection_size.min(right_reflection_size);
 
-            for row in pattern {
+            for row in pattern.rows() {
                 let left_reflection = &row[..reflection];
                 let right_reflection = &row[reflection..];
                 let left_reflection =
@@ -93,15 +97,17 @@ impl Pattern {
         reflections
     }
 
-    fn summarize(&self) -> Option<ReflectionLine> {
-        self.summarize_ignoring(None)
+    /// The pattern's reflection line.
+    pub fn summarize(&self) -> ReflectionLine {
+        self.reflection_line
     }
 
-    fn summarize_ignoring(
-        &self,
+    fn find_reflection_line(
+        pattern: &Grid<char>,
+        transposed_pattern: &Grid<char>,
         ignore_reflection_line: Option<ReflectionLine>,
     ) -> Option<ReflectionLine> {
-        for vertical_reflection in Self::find_reflections(&self.pattern) {
+        for vertical_reflection in Self::find_reflections(pattern) {
             let found_reflection = ReflectionLine {
                 orientation: Orientation::Vertical,
                 index: vertical_reflection,
@@ -115,7 +121,7 @@ impl Pattern {
             }
         }
 
-        for horizontal_reflection in Self::find_reflections(&self.transposed_pattern) {
+        for horizontal_reflection in Self::find_reflections(transposed_pattern) {
             let found_reflection = ReflectionLine {
                 orientation: Orientation::Horizontal,
                 index: horizontal_reflection,
@@ -132,69 +138,104 @@ impl Pattern {
         None
     }
 
-    fn summarize_after_smudge_fix(&mut self) -> ReflectionLine {
-        let initial_reflection_line = self.summarize().unwrap();
-
-        for i in 0..self.pattern.len() {
-            for j in 0..self.pattern[0].len() {
-                self.flip_smudge(i, j);
-                let new_summary = self.summarize_ignoring(Some(initial_reflection_line));
-                // Flip it back, so that the pattern is back to its initial state.
-                self.flip_smudge(i, j);
-
-                if let Some(new_reflection_line) = new_summary {
-                    return new_reflection_line;
-                }
+    /// Finds the pattern's new reflection line once its single smudge is fixed. Reports the
+    /// pattern if it has no smudge.
+    pub fn summarize_after_smudge_fix(&mut self) -> ParseResult<ReflectionLine> {
+        let initial_reflection_line = self.reflection_line;
+
+        for position in self.pattern.positions() {
+            self.flip_smudge(position);
+            let new_summary = Self::find_reflection_line(
+                &self.pattern,
+                &self.transposed_pattern,
+                Some(initial_reflection_line),
+            );
+            // Flip it back, so that the pattern is back to its initial state.
+            self.flip_smudge(position);
+
+            if let Some(new_reflection_line) = new_summary {
+                return Ok(new_reflection_line);
             }
         }
 
-        unreachable!()
+        Err(ParseError::new(
+            self.first_line,
+            "a pattern with a smudge giving it another reflection line",
+        ))
     }
 }
 
 pub struct Day13Solver {}
 
 impl Day13Solver {
-    fn generate_patterns_list(lines: Lines) -> Vec<Pattern> {
+    /// Parses all patterns, which are separated by empty lines.
+    pub fn generate_patterns_list(lines: Lines) -> ParseResult<Vec<Pattern>> {
         let mut patterns = Vec::new();
-        let mut curr_pattern = Vec::new();
-        for line in lines {
-            if line.is_empty() {
-                patterns.push(curr_pattern.clone());
-                curr_pattern.clear();
-                continue;
-            }
-
-            curr_pattern.push(line.chars().collect::<Vec<_>>());
+        let mut lines = lines.peekable();
+        while let Some(first_line) = lines.peek().copied() {
+            let pattern_lines = lines.by_ref().take_while(|line| !line.is_empty());
+            let pattern = Grid::parse_rows(pattern_lines, |_, line| {
+                parse::chars(line, "`#` or `.`", |character| {
+                    matches!(character, '#' | '.').then_some(character)
+                })
+                .collect()
+            })?;
+            patterns.push(Pattern::new(first_line, pattern)?);
         }
-        patterns.push(curr_pattern);
 
-        patterns.into_iter().map(Pattern::new).collect()
+        Ok(patterns)
     }
 }
 
 impl Solver for Day13Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day13/input.txt").unwrap();
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 13;
 
-        let patterns = Self::generate_patterns_list(file.lines());
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let patterns = Self::generate_patterns_list(input.lines())?;
         let mut sum_of_summaries = 0;
         for pattern in patterns {
-            sum_of_summaries += pattern.summarize().unwrap().value();
+            sum_of_summaries += pattern.summarize().value();
         }
 
-        println!("The sum of all summaries is {sum_of_summaries}");
+        Ok(sum_of_summaries.into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day13/input.txt").unwrap();
-
-        let patterns = Self::generate_patterns_list(file.lines());
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let patterns = Self::generate_patterns_list(input.lines())?;
         let mut sum_of_summaries = 0;
         for mut pattern in patterns {
-            sum_of_summaries += pattern.summarize_after_smudge_fix().value();
+            sum_of_summaries += pattern.summarize_after_smudge_fix()?.value();
         }
 
-        println!("The sum of all summaries is {sum_of_summaries}");
+        Ok(sum_of_summaries.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Self::generate_patterns_list(input.lines())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day13Solver::solve_part1(EXAMPLE).unwrap().to_string(),
+            "405"
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day13Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "400"
+        );
     }
 }
@@ -1,7 +1,11 @@
-use crate::solver::Solver;
-
-#[derive(Copy, Clone, PartialEq)]
-enum Object {
+use crate::cycle;
+use crate::geometry::Position;
+use crate::grid::Grid;
+use crate::parse::ParseResult;
+use crate::solver::{Answer, Solver};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Object {
     RoundedRock,
     CubeRock,
     Empty,
@@ -17,13 +21,13 @@ impl Object {
     }
 }
 
-impl From<char> for Object {
-    fn from(value: char) -> Self {
+impl Object {
+    pub fn from_char(value: char) -> Option<Self> {
         match value {
-            'O' => Self::RoundedRock,
-            '#' => Self::CubeRock,
-            '.' => Self::Empty,
-            _ => unreachable!(),
+            'O' => Some(Self::RoundedRock),
+            '#' => Some(Self::CubeRock),
+            '.' => Some(Self::Empty),
+            _ => None,
         }
     }
 }
@@ -38,140 +42,68 @@ impl From<&Object> for char {
     }
 }
 
-struct Platform {
-    platform: Vec<Vec<Object>>,
-    height: usize,
-    width: usize,
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Platform {
+    platform: Grid<Object>,
 }
 
 impl Platform {
-    fn new(platform: Vec<Vec<Object>>) -> Self {
-        let height = platform.len();
-        let width = platform[0].len();
-        Self {
-            platform,
-            height,
-            width,
-        }
+    pub fn new(platform: Grid<Object>) -> Self {
+        Self { platform }
     }
 
-    fn tilt_north(&mut self) {
-        let mut north_roll_per_column = vec![0; self.height];
-
-        for i in 0..self.height {
-            for j in 0..self.width {
-                let maximum_roll = north_roll_per_column[j];
-                let object = self.platform[i][j];
-                if object.is_empty() {
-                    continue;
-                }
-
-                let mut object_distance_from_north = i;
-                if object.is_rounded_rock() {
-                    if i > maximum_roll {
-                        object_distance_from_north = maximum_roll;
-                        self.platform[object_distance_from_north][j] = Object::RoundedRock;
-                        self.platform[i][j] = Object::Empty;
-                    }
-                }
-
-                north_roll_per_column[j] = object_distance_from_north + 1;
-            }
-        }
+    /// Parses the platform, a row of objects per line.
+    pub fn parse(input: &str) -> ParseResult<Self> {
+        Ok(Self::new(Grid::parse(
+            input,
+            "an object",
+            Object::from_char,
+        )?))
     }
 
-    fn tilt_west(&mut self) {
-        let mut west_roll_per_row = vec![0; self.width];
+    pub fn tilt_north(&mut self) {
+        let mut north_roll_per_column = vec![0; self.platform.num_cols()];
 
-        for j in 0..self.width {
-            for i in 0..self.height {
-                let maximum_roll = west_roll_per_row[i];
-                let object = self.platform[i][j];
+        for i in 0..self.platform.num_rows() {
+            for (j, north_roll) in north_roll_per_column.iter_mut().enumerate() {
+                let maximum_roll = *north_roll;
+                let object = self.platform[Position::new(i, j)];
                 if object.is_empty() {
                     continue;
                 }
 
-                let mut object_distance_from_west = j;
-                if object.is_rounded_rock() {
-                    if j > maximum_roll {
-                        object_distance_from_west = maximum_roll;
-                        self.platform[i][object_distance_from_west] = Object::RoundedRock;
-                        self.platform[i][j] = Object::Empty;
-                    }
+                let mut object_distance_from_north = i;
+                if object.is_rounded_rock() && i > maximum_roll {
+                    object_distance_from_north = maximum_roll;
+                    self.platform[Position::new(object_distance_from_north, j)] =
+                        Object::RoundedRock;
+                    self.platform[Position::new(i, j)] = Object::Empty;
                 }
 
-                west_roll_per_row[i] = object_distance_from_west + 1;
+                *north_roll = object_distance_from_north + 1;
             }
         }
     }
 
-    fn tilt_south(&mut self) {
-        let mut south_roll_per_column = vec![self.height - 1; self.height];
-
-        for i in (0..self.height).rev() {
-            for j in 0..self.width {
-                let maximum_roll = south_roll_per_column[j];
-                let object = self.platform[i][j];
-                if object.is_empty() {
-                    continue;
-                }
-
-                let mut object_distance_from_south = i;
-                if object.is_rounded_rock() {
-                    if i < maximum_roll {
-                        object_distance_from_south = maximum_roll;
-                        self.platform[object_distance_from_south][j] = Object::RoundedRock;
-                        self.platform[i][j] = Object::Empty;
-                    }
-                }
-
-                south_roll_per_column[j] = object_distance_from_south - 1;
-            }
+    /// Tilts the platform North, West, South, and then East.
+    ///
+    /// Each tilt is a tilt North, followed by a clockwise rotation which brings the next side to
+    /// tilt towards North. After the four of them, the platform is back to its initial
+    /// orientation.
+    pub fn spin_cycle(&mut self) {
+        for _ in 0..4 {
+            self.tilt_north();
+            self.platform = self.platform.rotate_clockwise();
         }
     }
 
-    fn tilt_east(&mut self) {
-        let mut east_roll_per_row = vec![self.width - 1; self.width];
-
-        for j in (0..self.width).rev() {
-            for i in 0..self.height {
-                let maximum_roll = east_roll_per_row[i];
-                let object = self.platform[i][j];
-                if object.is_empty() {
-                    continue;
-                }
-
-                let mut object_distance_from_east = j;
-                if object.is_rounded_rock() {
-                    if j < maximum_roll {
-                        object_distance_from_east = maximum_roll;
-                        self.platform[i][object_distance_from_east] = Object::RoundedRock;
-                        self.platform[i][j] = Object::Empty;
-                    }
-                }
-
-                east_roll_per_row[i] = object_distance_from_east - 1;
-            }
-        }
-    }
-
-    fn spin_cycle(&mut self) {
-        self.tilt_north();
-        self.tilt_west();
-        self.tilt_south();
-        self.tilt_east();
-    }
-
-    fn calculate_total_load(&self) -> u64 {
+    /// The load on the North support beams.
+    pub fn calculate_total_load(&self) -> u64 {
         let mut total_load = 0;
 
-        for i in 0..self.height {
-            for j in 0..self.width {
-                let object = self.platform[i][j];
-
-                if object.is_rounded_rock() {
-                    total_load += (self.height - i) as u64;
-                }
+        for (position, object) in self.platform.iter() {
+            if object.is_rounded_rock() {
+                total_load += (self.platform.num_rows() - position.row) as u64;
             }
         }
 
@@ -179,46 +111,59 @@ impl Platform {
     }
 }
 
+const SPIN_CYCLES: usize = 1_000_000_000;
+
 pub struct Day14Solver {}
 
 impl Solver for Day14Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day14/input.txt").unwrap();
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 14;
 
-        let mut platform = Platform::new(
-            file.lines()
-                .map(|line| line.chars().map(Object::from).collect())
-                .collect(),
-        );
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let mut platform = Platform::parse(input)?;
 
         platform.tilt_north();
 
-        println!(
-            "The total load on the platform after tilting it North is {}",
-            platform.calculate_total_load()
-        );
+        Ok(platform.calculate_total_load().into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day14/input.txt").unwrap();
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let platform = Platform::parse(input)?;
 
-        let mut platform = Platform::new(
-            file.lines()
-                .map(|line| line.chars().map(Object::from).collect())
-                .collect(),
-        );
-
-        println!("Initial load: {}", platform.calculate_total_load());
-        for i in 1..=1_000_000_000 {
+        // The platform soon ends up going through the same positions over and over, see the
+        // README.
+        let spin_cycle = |platform: &Platform| {
+            let mut platform = platform.clone();
             platform.spin_cycle();
-            if i % 100_000 == 0 {
-                println!("Load after {i} cycle: {}", platform.calculate_total_load());
-            }
-        }
+            platform
+        };
+        let platform = cycle::nth(platform, spin_cycle, SPIN_CYCLES);
 
-        println!(
-            "The total load on the platform after going through 1B cycles is {}",
-            platform.calculate_total_load()
+        Ok(platform.calculate_total_load().into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Platform::parse(input)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day14Solver::solve_part1(EXAMPLE).unwrap().to_string(),
+            "136"
         );
     }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day14Solver::solve_part2(EXAMPLE).unwrap().to_string(), "64");
+    }
 }
@@ -1,10 +1,12 @@
 use std::collections::VecDeque;
 
-use crate::solver::Solver;
+use crate::parse::{self, ParseResult};
+use crate::solver::{Answer, Solver};
 
 const NUM_BOXES: usize = 256;
 
-fn hash(string: &[u8]) -> usize {
+/// The Holiday ASCII String Helper algorithm.
+pub fn hash(string: &[u8]) -> usize {
     let mut output: usize = 0;
     for byte in string {
         output += *byte as usize;
@@ -28,17 +30,17 @@ enum Op<'a> {
 }
 
 impl<'a> Op<'a> {
-    fn new(instruction: &'a str) -> Self {
-        if instruction.ends_with('-') {
-            Self::Min(instruction.strip_suffix('-').unwrap().as_bytes())
+    fn new(instruction: &'a str) -> ParseResult<Self> {
+        if let Some(label) = instruction.strip_suffix('-') {
+            Ok(Self::Min(label.as_bytes()))
         } else {
-            let (label, focal_length) = instruction.split_once('=').unwrap();
+            let (label, focal_length) = parse::split_once(instruction, "=")?;
             let label = label.as_bytes();
-            let focal_length = focal_length.parse().unwrap();
-            Self::Equal(Lens {
+            let focal_length = parse::number(focal_length)?;
+            Ok(Self::Equal(Lens {
                 label,
                 focal_length,
-            })
+            }))
         }
     }
 
@@ -81,10 +83,18 @@ type Boxes<'a> = [Box<'a>; NUM_BOXES];
 pub struct Day15Solver {}
 
 impl Day15Solver {
-    fn solve_part2_with_file(file: &str) -> usize {
+    // Parses the operations of the initialization sequence.
+    fn parse_ops(file: &str) -> impl Iterator<Item = ParseResult<Op<'_>>> {
+        // The trailing newline, if any, is not part of the sequence.
+        file.trim_end().split(',').map(Op::new)
+    }
+
+    /// Runs the initialization sequence, and returns the focusing power of all lenses.
+    pub fn solve_part2_with_file(file: &str) -> ParseResult<usize> {
         let mut boxes: Boxes = std::array::from_fn(|_| Box::default());
 
-        for op in file.split(',').map(Op::new) {
+        for op in Self::parse_ops(file) {
+            let op = op?;
             let box_index = op.get_box_index();
             let selected_box = &mut boxes[box_index];
 
@@ -108,37 +118,69 @@ impl Day15Solver {
             }
         }
 
-        focusing_power
+        Ok(focusing_power)
     }
 }
 
 impl Solver for Day15Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day15/input.txt").unwrap();
-        let sum: usize = file
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 15;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        // The trailing newline, if any, is not part of the sequence.
+        Ok(input
+            .trim_end()
             .split(',')
             .map(|instruction| instruction.as_bytes())
             .map(hash)
-            .sum();
-        println!("{}", sum);
+            .sum::<usize>()
+            .into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day15/input.txt").unwrap();
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_part2_with_file(input)?.into())
+    }
 
-        let focusing_power = Self::solve_part2_with_file(&file);
-        println!("The focusing power of all these boxes is {focusing_power}");
+    fn parse(input: &str) -> ParseResult<()> {
+        // Part 1 hashes any instruction, so only part 2 can reject them.
+        for op in Self::parse_ops(input) {
+            op?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day15Solver::solve_part1(EXAMPLE).unwrap().to_string(),
+            "1320"
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day15Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "145"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "nightly"))]
+mod benches {
+    use super::*;
     use test::Bencher;
 
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        let file = std::fs::read_to_string("src/day15/input.txt").unwrap();
+        let file = crate::bench_input(15);
 
         b.iter(|| Day15Solver::solve_part2_with_file(&file));
     }
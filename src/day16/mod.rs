@@ -1,9 +1,10 @@
 use smallvec::SmallVec;
 use std::str::Lines;
 
-use crate::solver::Solver;
-
-const GRID_SIZE: usize = 110;
+use crate::geometry::{Direction, Position};
+use crate::grid;
+use crate::parse::{self, ParseResult};
+use crate::solver::{Answer, Solver};
 
 #[derive(Clone, Copy, Debug)]
 enum CellType {
@@ -14,62 +15,29 @@ enum CellType {
     VerticalSplitter,
 }
 
-impl From<char> for CellType {
-    fn from(value: char) -> Self {
-        match value {
+impl CellType {
+    fn from_char(value: char) -> Option<Self> {
+        let cell_type = match value {
             '.' => Self::EmptySpace,
             '\\' => Self::LeftMirror,
             '/' => Self::RightMirror,
             '-' => Self::HorizontalSplitter,
             '|' => Self::VerticalSplitter,
-            _ => unreachable!(),
-        }
-    }
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Right,
-    Bottom,
-    Left,
-}
+            _ => return None,
+        };
 
-impl Direction {
-    fn opposite(&self) -> Direction {
-        match self {
-            Self::Up => Self::Bottom,
-            Self::Right => Self::Left,
-            Self::Bottom => Self::Up,
-            Self::Left => Self::Right,
-        }
+        Some(cell_type)
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-struct Position {
-    row: usize,
-    col: usize,
-}
-
-macro_rules! pos {
-    ($row:expr, $col:expr) => {
-        Position {
-            row: $row,
-            col: $col,
-        }
-    };
-}
-
 #[derive(Debug)]
 struct Cell {
     cell_type: CellType,
-    energized: SmallVec<[Direction; std::mem::variant_count::<Direction>()]>,
+    energized: SmallVec<[Direction; Direction::ALL.len()]>,
 }
 
-impl From<char> for Cell {
-    fn from(value: char) -> Self {
-        let cell_type = value.into();
+impl From<CellType> for Cell {
+    fn from(cell_type: CellType) -> Self {
         Self {
             cell_type,
             energized: SmallVec::new(),
@@ -78,8 +46,8 @@ impl From<char> for Cell {
 }
 
 impl Cell {
-    fn interact(&self, in_light_beam: LightBeam) -> SmallVec<[LightBeam; 2]> {
-        let in_direction = in_light_beam.direction;
+    // Returns the directions of the light beams leaving the cell.
+    fn interact(&self, in_direction: Direction) -> SmallVec<[Direction; 2]> {
         let (first_out_direction, second_out_direction) = match self.cell_type {
             CellType::EmptySpace => (in_direction, None),
             CellType::LeftMirror => (Self::interact_left_mirror(in_direction), None),
@@ -88,31 +56,18 @@ impl Cell {
             CellType::VerticalSplitter => Self::interact_vertical_splitter(in_direction),
         };
 
-        let mut out_light_beams = SmallVec::new();
-        if let Some(out_light_beam) =
-            LightBeam::try_out(in_light_beam.position, first_out_direction)
-        {
-            out_light_beams.push(out_light_beam);
-        }
-
-        if second_out_direction.is_none() {
-            return out_light_beams;
-        }
+        let mut out_directions = SmallVec::new();
+        out_directions.push(first_out_direction);
+        out_directions.extend(second_out_direction);
 
-        if let Some(out_light_beam) =
-            LightBeam::try_out(in_light_beam.position, second_out_direction.unwrap())
-        {
-            out_light_beams.push(out_light_beam);
-        }
-
-        out_light_beams
+        out_directions
     }
 
     fn interact_left_mirror(in_direction: Direction) -> Direction {
         match in_direction {
             Direction::Up => Direction::Left,
-            Direction::Right => Direction::Bottom,
-            Direction::Bottom => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Right,
             Direction::Left => Direction::Up,
         }
     }
@@ -120,98 +75,45 @@ impl Cell {
     fn interact_horizontal_splitter(in_direction: Direction) -> (Direction, Option<Direction>) {
         match in_direction {
             Direction::Left | Direction::Right => (in_direction, None),
-            Direction::Up | Direction::Bottom => (Direction::Left, Some(Direction::Right)),
+            Direction::Up | Direction::Down => (Direction::Left, Some(Direction::Right)),
         }
     }
 
     fn interact_vertical_splitter(in_direction: Direction) -> (Direction, Option<Direction>) {
         match in_direction {
-            Direction::Up | Direction::Bottom => (in_direction, None),
-            Direction::Left | Direction::Right => (Direction::Up, Some(Direction::Bottom)),
+            Direction::Up | Direction::Down => (in_direction, None),
+            Direction::Left | Direction::Right => (Direction::Up, Some(Direction::Down)),
         }
     }
 }
 
 #[derive(Copy, Clone, Debug)]
-struct LightBeam {
-    position: Position,
-    direction: Direction,
-}
-
-impl LightBeam {
-    fn try_out(in_position: Position, out_direction: Direction) -> Option<Self> {
-        let Position {
-            row: in_row,
-            col: in_col,
-        } = in_position;
-
-        match out_direction {
-            Direction::Up => {
-                if in_row == 0 {
-                    None
-                } else {
-                    Some(Self {
-                        position: pos!(in_row - 1, in_col),
-                        direction: out_direction,
-                    })
-                }
-            }
-            Direction::Right => {
-                if in_col == GRID_SIZE - 1 {
-                    None
-                } else {
-                    Some(Self {
-                        position: pos!(in_row, in_col + 1),
-                        direction: out_direction,
-                    })
-                }
-            }
-            Direction::Bottom => {
-                if in_row == GRID_SIZE - 1 {
-                    None
-                } else {
-                    Some(Self {
-                        position: pos!(in_row + 1, in_col),
-                        direction: out_direction,
-                    })
-                }
-            }
-            Direction::Left => {
-                if in_col == 0 {
-                    None
-                } else {
-                    Some(Self {
-                        position: pos!(in_row, in_col - 1),
-                        direction: out_direction,
-                    })
-                }
-            }
-        }
-    }
+pub struct LightBeam {
+    pub position: Position,
+    pub direction: Direction,
 }
 
 #[derive(Debug)]
-struct Grid {
-    cells: Vec<Vec<Cell>>,
+pub struct Grid {
+    cells: grid::Grid<Cell>,
 }
 
 impl Grid {
-    fn new(lines: Lines) -> Self {
-        let cells = lines
-            .map(|line| line.chars().map(Cell::from).collect())
-            .collect();
-        Self { cells }
-    }
-
-    fn get_cell_mut(&mut self, position: Position) -> &mut Cell {
-        &mut self.cells[position.row][position.col]
-    }
-
-    fn light_beam(&mut self, light_beam: LightBeam) -> usize {
-        let mut light_beams = vec![light_beam.clone()];
+    pub fn new(lines: Lines) -> ParseResult<Self> {
+        let cells = grid::Grid::parse_rows(lines, |_, line| {
+            parse::chars(line, "a cell", CellType::from_char)
+                .map(|cell_type| cell_type.map(Cell::from))
+                .collect()
+        })?;
+        Ok(Self { cells })
+    }
+
+    /// Shines the light beam into the grid, and returns the number of energized cells.
+    pub fn light_beam(&mut self, light_beam: LightBeam) -> usize {
+        let mut light_beams = vec![light_beam];
         let mut num_cells_energized = 0;
         while let Some(in_light_beam) = light_beams.pop() {
-            let cell = self.get_cell_mut(in_light_beam.position);
+            let cell = &mut self.cells[in_light_beam.position];
             if cell.energized.contains(&in_light_beam.direction) {
                 continue;
             }
@@ -221,17 +123,22 @@ impl Grid {
             }
 
             cell.energized.push(in_light_beam.direction);
-            light_beams.extend(cell.interact(in_light_beam));
+            for out_direction in cell.interact(in_light_beam.direction) {
+                if let Some(out_position) = self.cells.step(in_light_beam.position, out_direction) {
+                    light_beams.push(LightBeam {
+                        position: out_position,
+                        direction: out_direction,
+                    });
+                }
+            }
         }
 
         num_cells_energized
     }
 
-    fn de_energize(&mut self) {
-        for row in &mut self.cells {
-            for cell in row {
-                cell.energized.clear();
-            }
+    pub fn de_energize(&mut self) {
+        for (_, cell) in self.cells.iter_mut() {
+            cell.energized.clear();
         }
     }
 }
@@ -239,83 +146,104 @@ impl Grid {
 pub struct Day16Solver {}
 
 impl Day16Solver {
-    fn solve_part1_with_lines(lines: Lines) -> usize {
-        let mut grid = Grid::new(lines);
-        grid.light_beam(LightBeam {
-            position: pos!(0, 0),
+    pub fn solve_part1_with_lines(lines: Lines) -> ParseResult<usize> {
+        let mut grid = Grid::new(lines)?;
+        Ok(grid.light_beam(LightBeam {
+            position: Position::new(0, 0),
             direction: Direction::Right,
-        })
+        }))
     }
 
-    fn solve_part2_with_lines(lines: Lines) -> usize {
+    pub fn solve_part2_with_lines(lines: Lines) -> ParseResult<usize> {
         let mut max_energized_cells = 0;
-        let mut grid = Grid::new(lines);
+        let mut grid = Grid::new(lines)?;
 
-        for i in 0..GRID_SIZE {
+        for i in 0..grid.cells.num_cols() {
             let top_light_beam = LightBeam {
-                position: pos!(GRID_SIZE - 1, i),
+                position: Position::new(grid.cells.num_rows() - 1, i),
                 direction: Direction::Up,
             };
             max_energized_cells = max_energized_cells.max(grid.light_beam(top_light_beam));
             grid.de_energize();
 
-            let right_light_beam = LightBeam {
-                position: pos!(i, 0),
-                direction: Direction::Right,
+            let bottom_light_beam = LightBeam {
+                position: Position::new(0, i),
+                direction: Direction::Down,
             };
-            max_energized_cells = max_energized_cells.max(grid.light_beam(right_light_beam));
+            max_energized_cells = max_energized_cells.max(grid.light_beam(bottom_light_beam));
             grid.de_energize();
+        }
 
-            let bottom_light_beam = LightBeam {
-                position: pos!(0, i),
-                direction: Direction::Bottom,
+        for i in 0..grid.cells.num_rows() {
+            let right_light_beam = LightBeam {
+                position: Position::new(i, 0),
+                direction: Direction::Right,
             };
-            max_energized_cells = max_energized_cells.max(grid.light_beam(bottom_light_beam));
+            max_energized_cells = max_energized_cells.max(grid.light_beam(right_light_beam));
             grid.de_energize();
 
             let left_light_beam = LightBeam {
-                position: pos!(i, GRID_SIZE - 1),
+                position: Position::new(i, grid.cells.num_cols() - 1),
                 direction: Direction::Left,
             };
             max_energized_cells = max_energized_cells.max(grid.light_beam(left_light_beam));
             grid.de_energize();
         }
 
-        max_energized_cells
+        Ok(max_energized_cells)
     }
 }
 
 impl Solver for Day16Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day16/input.txt").unwrap();
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 16;
 
-        let num_energized_cells = Self::solve_part1_with_lines(file.lines());
-        println!("There are {num_energized_cells} energized cells.");
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_part1_with_lines(input.lines())?.into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day16/input.txt").unwrap();
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_part2_with_lines(input.lines())?.into())
+    }
 
-        let max_energized_cells = Self::solve_part2_with_lines(file.lines());
-        println!("The maximum number energized in a given configuration is {max_energized_cells}");
+    fn parse(input: &str) -> ParseResult<()> {
+        Grid::new(input.lines())?;
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day16Solver::solve_part1(EXAMPLE).unwrap().to_string(), "46");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day16Solver::solve_part2(EXAMPLE).unwrap().to_string(), "51");
+    }
+}
+
+#[cfg(all(test, feature = "nightly"))]
+mod benches {
+    use super::*;
     use test::Bencher;
 
     #[bench]
     fn bench_part1(b: &mut Bencher) {
-        let file = std::fs::read_to_string("src/day16/input.txt").unwrap();
+        let file = crate::bench_input(16);
 
         b.iter(|| Day16Solver::solve_part1_with_lines(file.lines()));
     }
 
     #[bench]
     fn bench_part2(b: &mut Bencher) {
-        let file = std::fs::read_to_string("src/day16/input.txt").unwrap();
+        let file = crate::bench_input(16);
 
         b.iter(|| Day16Solver::solve_part2_with_lines(file.lines()));
     }
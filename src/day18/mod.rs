@@ -1,131 +1,122 @@
 use std::fmt::Debug;
 
-use crate::solver::Solver;
-
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn from1(value: &str) -> Self {
-        match value {
-            "U" => Self::Up,
-            "D" => Self::Down,
-            "L" => Self::Left,
-            "R" => Self::Right,
-            _ => unreachable!(),
-        }
+use crate::geometry::{Direction, Point};
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
+
+/// Parses the direction of an instruction from its letter.
+fn parse_direction1(value: &str) -> ParseResult<Direction> {
+    match value {
+        "U" => Ok(Direction::Up),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        "R" => Ok(Direction::Right),
+        _ => Err(ParseError::new(value, "`U`, `D`, `L` or `R`")),
     }
+}
 
-    fn from2(value: char) -> Self {
-        match value {
-            '0' => Self::Right,
-            '1' => Self::Down,
-            '2' => Self::Left,
-            '3' => Self::Up,
-            _ => unreachable!(),
-        }
+/// Parses the direction of an instruction from the last digit of its colour code.
+fn parse_direction2(value: &str) -> ParseResult<Direction> {
+    match value {
+        "0" => Ok(Direction::Right),
+        "1" => Ok(Direction::Down),
+        "2" => Ok(Direction::Left),
+        "3" => Ok(Direction::Up),
+        _ => Err(ParseError::new(value, "`0`, `1`, `2` or `3`")),
     }
 }
 
 #[derive(Debug)]
-struct Instruction {
-    direction: Direction,
-    distance: i64,
+pub struct Instruction {
+    pub direction: Direction,
+    pub distance: i64,
 }
 
 impl Instruction {
-    fn from1(value: &str) -> Self {
-        let (direction, distance_and_colour) = value.split_once(' ').unwrap();
-        let (distance, _) = distance_and_colour.split_once(' ').unwrap();
-
-        Instruction {
-            direction: Direction::from1(direction),
-            distance: distance.parse().unwrap(),
-        }
+    /// Parses the instruction from its direction and distance.
+    pub fn from1(value: &str) -> ParseResult<Self> {
+        let (direction, distance_and_colour) = parse::split_once(value, " ")?;
+        let (distance, _) = parse::split_once(distance_and_colour, " ")?;
+
+        Ok(Instruction {
+            direction: parse_direction1(direction)?,
+            distance: parse::number(distance)?,
+        })
     }
 
-    fn from2(value: &str) -> Self {
-        let (_, suffix) = value.rsplit_once(' ').unwrap();
-        let suffix = suffix.strip_prefix("(#").unwrap();
-        let suffix = suffix.strip_suffix(')').unwrap();
-
-        let instruction: Vec<_> = suffix.chars().collect();
-        let distance = &instruction[..5];
-        let distance = i64::from_str_radix(&String::from_iter(distance), 16).unwrap();
-        let direction = Direction::from2(instruction[5]);
-
-        Instruction {
+    /// Parses the instruction from its hexadecimal colour code.
+    pub fn from2(value: &str) -> ParseResult<Self> {
+        let (_, suffix) = value
+            .rsplit_once(' ')
+            .ok_or_else(|| ParseError::new(value, "a colour code"))?;
+        let suffix = parse::strip_prefix(suffix, "(#")?;
+        let instruction = suffix
+            .strip_suffix(')')
+            .filter(|instruction| instruction.len() == 6 && instruction.is_ascii())
+            .ok_or_else(|| ParseError::new(suffix, "6 hexadecimal digits followed by `)`"))?;
+
+        let (distance, direction) = instruction.split_at(5);
+        let distance = i64::from_str_radix(distance, 16)
+            .map_err(|_| ParseError::new(distance, "a hexadecimal distance"))?;
+        let direction = parse_direction2(direction)?;
+
+        Ok(Instruction {
             direction,
             distance,
-        }
+        })
     }
 }
 
 #[derive(Debug)]
-struct Coordinate {
-    x: i64,
-    y: i64,
-}
-
-#[derive(Debug)]
-struct Trench2 {
-    vertices: Vec<Coordinate>,
+pub struct Trench2 {
+    pub vertices: Vec<Point>,
     // Holds the length of the trench.
-    length: i64,
+    pub length: i64,
 }
 
 impl Trench2 {
-    fn dig<I>(instructions: I) -> Self
+    /// Digs the trench by following the instructions.
+    pub fn dig<I>(instructions: I) -> ParseResult<Self>
     where
-        I: Iterator<Item = Instruction>,
+        I: Iterator<Item = ParseResult<Instruction>>,
     {
-        let mut x = 0;
-        let mut y = 0;
+        let mut position = Point::default();
         let mut vertices = Vec::new();
         let mut length = 0;
 
-        for Instruction {
-            direction,
-            distance,
-        } in instructions
-        {
-            match direction {
-                Direction::Down => x -= distance,
-                Direction::Up => x += distance,
-                Direction::Left => y -= distance,
-                Direction::Right => y += distance,
-            }
+        for instruction in instructions {
+            let Instruction {
+                direction,
+                distance,
+            } = instruction?;
+            position += direction.offset() * distance;
 
             length += distance;
-            vertices.push(Coordinate { x, y });
+            vertices.push(position);
         }
 
-        Self { vertices, length }
+        Ok(Self { vertices, length })
     }
 
     // Digs the interior area of the trench, and returns the total area of
     // the trench and its interior. This uses a combination of shoelace theorem
     // and Pick's theorem to figure out how many interior points there are.
-    fn dig_interior(&self) -> i64 {
+    pub fn dig_interior(&self) -> i64 {
         let num_vertices = self.vertices.len();
         let mut area = 0;
         for i in 0..num_vertices {
-            let xi = self.vertices[i].x;
-            let yp1 = self.vertices[(i + 1) % num_vertices].y;
+            let xi = self.vertices[i].row;
+            let yp1 = self.vertices[(i + 1) % num_vertices].col;
             let ym1 = if i == 0 {
-                self.vertices[num_vertices - 1].y
+                self.vertices[num_vertices - 1].col
             } else {
-                self.vertices[i - 1].y
+                self.vertices[i - 1].col
             };
 
             area += xi * (yp1 - ym1);
         }
-        area /= 2;
+        // The sign of the area depends on whether the trench is dug clockwise or not.
+        area = area.abs() / 2;
 
         let interior_points = area - (self.length / 2) + 1;
 
@@ -136,25 +127,44 @@ impl Trench2 {
 pub struct Day18Solver {}
 
 impl Solver for Day18Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day18/input.txt").unwrap();
-
-        let trench = Trench2::dig(file.lines().map(Instruction::from1));
-        let trench_and_interior_area = trench.dig_interior();
-        println!(
-            "The area contained by the trench is {}",
-            trench_and_interior_area
-        );
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 18;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let trench = Trench2::dig(input.lines().map(Instruction::from1))?;
+        Ok(trench.dig_interior().into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let trench = Trench2::dig(input.lines().map(Instruction::from2))?;
+        Ok(trench.dig_interior().into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day18/input.txt").unwrap();
+    fn parse(input: &str) -> ParseResult<()> {
+        for line in input.lines() {
+            Instruction::from1(line)?;
+            Instruction::from2(line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day18Solver::solve_part1(EXAMPLE).unwrap().to_string(), "62");
+    }
 
-        let trench = Trench2::dig(file.lines().map(Instruction::from2));
-        let trench_and_interior_area = trench.dig_interior();
-        println!(
-            "The area contained by the trench is {}",
-            trench_and_interior_area
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day18Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "952408144115"
         );
     }
 }
@@ -1,6 +1,7 @@
 use std::{collections::HashMap, ops::RangeInclusive, str::Lines};
 
-use crate::solver::Solver;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 const INPUT_WORKFLOW_NAME: &str = "in";
 
@@ -11,15 +12,16 @@ enum ConditionalOperator {
 }
 
 #[derive(Debug)]
-struct Part {
-    x: i64,
-    m: i64,
-    a: i64,
-    s: i64,
+pub struct Part {
+    pub x: i64,
+    pub m: i64,
+    pub a: i64,
+    pub s: i64,
 }
 
 impl Part {
-    fn sum(&self) -> i64 {
+    /// The sum of the part's ratings.
+    pub fn sum(&self) -> i64 {
         self.x + self.m + self.a + self.s
     }
 
@@ -49,14 +51,14 @@ enum Field {
     S,
 }
 
-impl From<char> for Field {
-    fn from(value: char) -> Self {
+impl Field {
+    fn parse(value: &str) -> ParseResult<Self> {
         match value {
-            'x' => Field::X,
-            'm' => Field::M,
-            'a' => Field::A,
-            's' => Field::S,
-            _ => unreachable!(),
+            "x" => Ok(Field::X),
+            "m" => Ok(Field::M),
+            "a" => Ok(Field::A),
+            "s" => Ok(Field::S),
+            _ => Err(ParseError::new(value, "`x`, `m`, `a` or `s`")),
         }
     }
 }
@@ -74,7 +76,7 @@ struct Rule<'a> {
 }
 
 impl<'a> Rule<'a> {
-    fn satisfy(&self, part: &Part) -> RuleOutcome {
+    fn satisfy(&self, part: &Part) -> RuleOutcome<'_> {
         if self.predicate.is_none() {
             return self.success_outcome.clone();
         }
@@ -100,7 +102,7 @@ struct Workflow<'a> {
 }
 
 impl<'a> Workflow<'a> {
-    fn run(&self, part: &Part) -> RuleOutcome {
+    fn run(&self, part: &Part) -> RuleOutcome<'_> {
         for rule in &self.rules {
             let outcome = rule.satisfy(part);
 
@@ -176,8 +178,7 @@ impl PartCombinations {
                         (*field.start())..=(predicate.threshold),
                     )
                 } else {
-                    // 0..=-1 is an empty range.
-                    (field.clone(), 0..=-1)
+                    (field.clone(), RangeInclusive::new(1, 0))
                 }
             }
             ConditionalOperator::LesserThan => {
@@ -187,8 +188,7 @@ impl PartCombinations {
                         (predicate.threshold)..=(*field.end()),
                     )
                 } else {
-                    // 0..=-1 is an empty range.
-                    (field.clone(), 0..=-1)
+                    (field.clone(), RangeInclusive::new(1, 0))
                 }
             }
         };
@@ -215,7 +215,8 @@ impl PartCombinations {
     }
 }
 
-struct WorkflowResolver<'a> {
+/// Resolves parts through the workflows, starting from the `in` workflow.
+pub struct WorkflowResolver<'a> {
     workflows: HashMap<&'a str, Workflow<'a>>,
 }
 
@@ -225,7 +226,8 @@ struct CombinationState<'a> {
 }
 
 impl<'a> WorkflowResolver<'a> {
-    fn evaluate_all_combinations(&self) -> i64 {
+    /// Counts all combinations of ratings (from 1 to 4000) that would be accepted.
+    pub fn evaluate_all_combinations(&self) -> i64 {
         let mut number_of_combinations = 0;
 
         let mut workflows_to_visit = vec![CombinationState {
@@ -273,8 +275,8 @@ impl<'a> WorkflowResolver<'a> {
         number_of_combinations
     }
 
-    // Returns the sum of their ratings.
-    fn run<Ps>(&self, parts: Ps) -> i64
+    /// Returns the sum of the ratings of all accepted parts.
+    pub fn run<Ps>(&self, parts: Ps) -> i64
     where
         Ps: IntoIterator<Item = Part>,
     {
@@ -299,45 +301,38 @@ impl<'a> WorkflowResolver<'a> {
         total
     }
 
-    fn generate_predicate(condition: Option<&str>) -> Option<Predicate> {
-        if condition.is_none() {
-            return None;
-        }
-
-        let condition = condition.unwrap();
-        let ((field, threshold), conditional_operator) = if condition.contains('<') {
-            (
-                condition.split_once('<').unwrap(),
-                ConditionalOperator::LesserThan,
-            )
-        } else if condition.contains('>') {
-            (
-                condition.split_once('>').unwrap(),
-                ConditionalOperator::GreaterThan,
-            )
-        } else {
-            unreachable!()
-        };
-        let threshold = threshold.parse().unwrap();
-        let field = field.chars().next().unwrap().into();
-
-        Some(Predicate {
+    fn generate_predicate(condition: &str) -> ParseResult<Predicate> {
+        let ((field, threshold), conditional_operator) =
+            if let Some(field_and_threshold) = condition.split_once('<') {
+                (field_and_threshold, ConditionalOperator::LesserThan)
+            } else if let Some(field_and_threshold) = condition.split_once('>') {
+                (field_and_threshold, ConditionalOperator::GreaterThan)
+            } else {
+                return Err(ParseError::new(condition, "a condition with `<` or `>`"));
+            };
+        let threshold = parse::number(threshold)?;
+        let field = Field::parse(field)?;
+
+        Ok(Predicate {
             field,
             conditional_operator,
             threshold,
         })
     }
 
-    fn new(lines: &mut Lines<'a>) -> Self {
+    /// Parses the workflows, up to and including the empty line that follows them.
+    pub fn new(lines: &mut Lines<'a>) -> ParseResult<Self> {
         let mut workflows = HashMap::new();
-        loop {
-            let line = lines.next().unwrap();
+        let mut redirections = Vec::new();
+        for line in lines.by_ref() {
             if line.is_empty() {
                 break;
             }
 
-            let (workflow_name, workflow_rules) = line.split_once('{').unwrap();
-            let workflow_rules = workflow_rules.strip_suffix('}').unwrap();
+            let (workflow_name, workflow_rules) = parse::split_once(line, "{")?;
+            let workflow_rules = workflow_rules
+                .strip_suffix('}')
+                .ok_or_else(|| ParseError::new(workflow_rules, "rules followed by `}`"))?;
 
             let mut rules = Vec::new();
             for workflow_rule in workflow_rules.split(',') {
@@ -349,9 +344,12 @@ impl<'a> WorkflowResolver<'a> {
                 let success_outcome = match outcome {
                     "A" => RuleOutcome::Accepted,
                     "R" => RuleOutcome::Rejected,
-                    workflow_name => RuleOutcome::Redirected(workflow_name),
+                    workflow_name => {
+                        redirections.push(workflow_name);
+                        RuleOutcome::Redirected(workflow_name)
+                    }
                 };
-                let predicate = Self::generate_predicate(condition);
+                let predicate = condition.map(Self::generate_predicate).transpose()?;
 
                 rules.push(Rule {
                     predicate,
@@ -359,47 +357,49 @@ impl<'a> WorkflowResolver<'a> {
                 });
             }
 
+            // The last rule applies to the parts that no other rule applies to.
+            if rules.last().unwrap().predicate.is_some() {
+                let last_rule = workflow_rules.rsplit(',').next().unwrap();
+                return Err(ParseError::new(last_rule, "a rule without a condition"));
+            }
+
             let workflow = Workflow { rules };
             workflows.insert(workflow_name, workflow);
         }
 
-        Self { workflows }
+        if let Some(workflow_name) = redirections
+            .into_iter()
+            .find(|workflow_name| !workflows.contains_key(workflow_name))
+        {
+            return Err(ParseError::new(workflow_name, "the name of a workflow"));
+        }
+        if !workflows.contains_key(INPUT_WORKFLOW_NAME) {
+            // There is no offending text to point at.
+            let expected = format!("the `{INPUT_WORKFLOW_NAME}` workflow");
+            return Err(ParseError::new("", expected));
+        }
+
+        Ok(Self { workflows })
     }
 
-    fn generate_parts_list(lines: Lines) -> impl Iterator<Item = Part> + '_ {
+    /// Parses the parts that follow the workflows.
+    pub fn generate_parts_list(lines: Lines<'_>) -> impl Iterator<Item = ParseResult<Part>> + '_ {
         lines.map(|line| {
-            let line = line.strip_prefix('{').unwrap().strip_suffix('}').unwrap();
-            let mut field_and_values = line.split(',');
-            let x = field_and_values
-                .next()
-                .unwrap()
-                .strip_prefix("x=")
-                .unwrap()
-                .parse()
-                .unwrap();
-            let m = field_and_values
-                .next()
-                .unwrap()
-                .strip_prefix("m=")
-                .unwrap()
-                .parse()
-                .unwrap();
-            let a = field_and_values
-                .next()
-                .unwrap()
-                .strip_prefix("a=")
-                .unwrap()
-                .parse()
-                .unwrap();
-            let s = field_and_values
-                .next()
-                .unwrap()
-                .strip_prefix("s=")
-                .unwrap()
-                .parse()
-                .unwrap();
-
-            Part { x, m, a, s }
+            let ratings = parse::strip_prefix(line, "{")?;
+            let ratings = ratings
+                .strip_suffix('}')
+                .ok_or_else(|| ParseError::new(ratings, "ratings followed by `}`"))?;
+            let mut field_and_values = ratings.split(',');
+            let mut parse_rating = |prefix: &str| -> ParseResult<i64> {
+                let field_and_value = parse::next(&mut field_and_values, ratings, prefix)?;
+                parse::number(parse::strip_prefix(field_and_value, prefix)?)
+            };
+            let x = parse_rating("x=")?;
+            let m = parse_rating("m=")?;
+            let a = parse_rating("a=")?;
+            let s = parse_rating("s=")?;
+
+            Ok(Part { x, m, a, s })
         })
     }
 }
@@ -407,22 +407,51 @@ impl<'a> WorkflowResolver<'a> {
 pub struct Day19Solver {}
 
 impl Solver for Day19Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day19/input.txt").unwrap();
-        let mut lines = file.lines();
-        let resolver = WorkflowResolver::new(&mut lines);
-        let parts = WorkflowResolver::generate_parts_list(lines);
-        let sum_of_accepted_parts = resolver.run(parts);
-        println!("The sum of all approved parts is {}", sum_of_accepted_parts);
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 19;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let mut lines = input.lines();
+        let resolver = WorkflowResolver::new(&mut lines)?;
+        let parts =
+            WorkflowResolver::generate_parts_list(lines).collect::<ParseResult<Vec<_>>>()?;
+        Ok(resolver.run(parts).into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let resolver = WorkflowResolver::new(&mut input.lines())?;
+        Ok(resolver.evaluate_all_combinations().into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        let mut lines = input.lines();
+        WorkflowResolver::new(&mut lines)?;
+        for part in WorkflowResolver::generate_parts_list(lines) {
+            part?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day19Solver::solve_part1(EXAMPLE).unwrap().to_string(),
+            "19114"
+        );
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day19/input.txt").unwrap();
-        let resolver = WorkflowResolver::new(&mut file.lines());
-        let number_of_combinations = resolver.evaluate_all_combinations();
-        println!(
-            "The total number of valid combinations is {}",
-            number_of_combinations
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day19Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "167409079868000"
         );
     }
 }
@@ -1,74 +1,206 @@
-use crate::solver::Solver;
+use std::collections::BTreeMap;
+use std::fmt;
 
-const NUM_RED_CUBES: u32 = 12;
-const NUM_GREEN_CUBES: u32 = 13;
-const NUM_BLUE_CUBES: u32 = 14;
+use crate::parse::{self, ParseResult};
+use crate::solver::{Answer, Solver};
 
-pub struct Day2Solver {}
-impl Solver for Day2Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day2/input.txt").unwrap();
-
-        let mut total_valid_game_ids = 0;
-        'game: for line in file.lines() {
-            let (game_prefix, plays) = line.split_once(':').unwrap();
-            let (_, game_id) = game_prefix.split_once(' ').unwrap();
-            let game_id: u32 = game_id.parse().unwrap();
-
-            for play in plays.split(';') {
-                for draw in play.split(',') {
-                    let (num_drawn, colour) = draw.trim().split_once(' ').unwrap();
-                    let num_drawn: u32 = num_drawn.parse().unwrap();
-
-                    let max_drawn = match colour {
-                        "red" => NUM_RED_CUBES,
-                        "green" => NUM_GREEN_CUBES,
-                        "blue" => NUM_BLUE_CUBES,
-                        _ => unreachable!(),
-                    };
-
-                    if num_drawn > max_drawn {
-                        continue 'game;
-                    }
-                }
+// Parses cubes counted by colour, like `3 blue, 4 red`. Cubes may be of any colour, and the
+// counts of a colour listed several times are added up.
+fn parse_cubes(text: &str) -> ParseResult<BTreeMap<&str, u32>> {
+    let mut cubes = BTreeMap::new();
+    for count_and_colour in text.split(',') {
+        let (count, colour) = parse::split_once(count_and_colour.trim(), " ")?;
+        *cubes.entry(colour).or_default() += parse::number::<u32>(count)?;
+    }
+
+    Ok(cubes)
+}
+
+/// The cubes drawn at once from the bag, counted by colour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Draw<'a> {
+    pub cubes: BTreeMap<&'a str, u32>,
+}
+
+impl<'a> Draw<'a> {
+    /// Parses a draw like `3 blue, 4 red`.
+    pub fn parse(text: &'a str) -> ParseResult<Self> {
+        Ok(Self {
+            cubes: parse_cubes(text)?,
+        })
+    }
+}
+
+/// A game of drawing cubes from the bag, and putting them back, several times.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game<'a> {
+    pub id: u32,
+    pub draws: Vec<Draw<'a>>,
+}
+
+impl<'a> Game<'a> {
+    /// Parses a game like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+    pub fn parse(line: &'a str) -> ParseResult<Self> {
+        let (game_prefix, draws) = parse::split_once(line, ":")?;
+        let id = parse::number(parse::strip_prefix(game_prefix, "Game ")?)?;
+        let draws = draws
+            .split(';')
+            .map(Draw::parse)
+            .collect::<ParseResult<_>>()?;
+
+        Ok(Self { id, draws })
+    }
+
+    /// Whether the bag holds enough cubes of each colour for every draw of the game.
+    pub fn is_possible_with(&self, bag: &Bag) -> bool {
+        self.draws.iter().all(|draw| {
+            draw.cubes
+                .iter()
+                .all(|(colour, count)| *count <= bag.count(colour))
+        })
+    }
+
+    /// The bag with the fewest cubes of each colour which makes the game possible.
+    pub fn minimal_bag(&self) -> Bag<'a> {
+        let mut bag = Bag::default();
+        for draw in &self.draws {
+            for (colour, count) in &draw.cubes {
+                let bag_count = bag.cubes.entry(colour).or_default();
+                *bag_count = (*bag_count).max(*count);
             }
+        }
+
+        bag
+    }
+}
+
+/// The cubes in the bag, counted by colour.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bag<'a> {
+    pub cubes: BTreeMap<&'a str, u32>,
+}
 
-            total_valid_game_ids += game_id;
+impl<'a> Bag<'a> {
+    /// The bag the puzzle asks about.
+    pub fn puzzle() -> Self {
+        Self {
+            cubes: BTreeMap::from([("red", 12), ("green", 13), ("blue", 14)]),
         }
+    }
 
-        println!("The sum of valid game IDs is: {total_valid_game_ids}.");
+    /// Parses a bag written like a draw, e.g. `12 red, 13 green, 14 blue`.
+    pub fn parse(text: &'a str) -> ParseResult<Self> {
+        Ok(Self {
+            cubes: parse_cubes(text)?,
+        })
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day2/input.txt").unwrap();
+    /// The number of cubes of the colour, which is 0 for colours missing from the bag.
+    pub fn count(&self, colour: &str) -> u32 {
+        self.cubes.get(colour).copied().unwrap_or_default()
+    }
 
-        let mut sum_game_power_sets = 0;
-        for line in file.lines() {
-            let (_, plays) = line.split_once(':').unwrap();
+    /// The power of the bag, which is the product of its numbers of cubes of each colour.
+    pub fn power(&self) -> u64 {
+        self.cubes.values().map(|count| *count as u64).product()
+    }
+}
 
-            let mut min_red_cubes = 0;
-            let mut min_green_cubes = 0;
-            let mut min_blue_clubes = 0;
-            for play in plays.split(';') {
-                for draw in play.split(',') {
-                    let (num_drawn, colour) = draw.trim().split_once(' ').unwrap();
-                    let num_drawn: u32 = num_drawn.parse().unwrap();
+impl fmt::Display for Bag<'_> {
+    /// Writes the bag like a draw, with its colours in alphabetical order.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cubes: Vec<_> = self
+            .cubes
+            .iter()
+            .map(|(colour, count)| format!("{count} {colour}"))
+            .collect();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
 
-                    let min_cubes = match colour {
-                        "red" => &mut min_red_cubes,
-                        "green" => &mut min_green_cubes,
-                        "blue" => &mut min_blue_clubes,
-                        _ => unreachable!(),
-                    };
+pub struct Day2Solver {}
 
-                    *min_cubes = (*min_cubes).max(num_drawn);
-                }
+impl Day2Solver {
+    /// Sums the IDs of the games which are possible with the bag.
+    pub fn sum_possible_game_ids(input: &str, bag: &Bag) -> ParseResult<u32> {
+        let mut total_possible_game_ids = 0;
+        for line in input.lines() {
+            let game = Game::parse(line)?;
+            if game.is_possible_with(bag) {
+                total_possible_game_ids += game.id;
             }
+        }
+
+        Ok(total_possible_game_ids)
+    }
+}
 
-            // Definition of a game power set is the multiplication of all colours.
-            sum_game_power_sets += min_red_cubes * min_green_cubes * min_blue_clubes;
+impl Solver for Day2Solver {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 2;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        Ok(Self::sum_possible_game_ids(input, &Bag::puzzle())?.into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let mut sum_game_powers = 0;
+        for line in input.lines() {
+            sum_game_powers += Game::parse(line)?.minimal_bag().power();
         }
 
-        println!("The sum of power sets is: {sum_game_power_sets}.");
+        Ok(sum_game_powers.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        for line in input.lines() {
+            Game::parse(line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day2Solver::solve_part1(EXAMPLE).unwrap().to_string(), "8");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day2Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "2286"
+        );
+    }
+
+    #[test]
+    fn games_possible_with_another_bag() {
+        let bag = Bag::parse("4 red, 2 green, 6 blue, 7 yellow").unwrap();
+        let sum = Day2Solver::sum_possible_game_ids(EXAMPLE, &bag).unwrap();
+        assert_eq!(sum, 1);
+
+        let game = Game::parse(EXAMPLE.lines().next().unwrap()).unwrap();
+        assert_eq!(
+            game.minimal_bag(),
+            Bag::parse("4 red, 2 green, 6 blue").unwrap()
+        );
+        assert!(!game.is_possible_with(&Bag::parse("9 red, 9 green").unwrap()));
+    }
+
+    #[test]
+    fn cubes_of_any_colour() {
+        let game = Game::parse("Game 7: 2 yellow, 1 red; 3 yellow, 2 blue, 1 yellow").unwrap();
+        let bag = game.minimal_bag();
+
+        assert_eq!(bag.to_string(), "2 blue, 1 red, 4 yellow");
+        assert_eq!(bag.power(), 8);
+        assert!(!game.is_possible_with(&Bag::puzzle()));
+        assert!(Game::parse("Game 8: 2 ").is_err());
     }
 }
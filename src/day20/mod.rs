@@ -3,7 +3,9 @@ use std::{
     str::Lines,
 };
 
-use crate::solver::Solver;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Part, Solver};
+use crate::trace;
 
 const BROADCASTER_ID: &str = "broadcaster";
 const BUTTON_ID: &str = "button";
@@ -126,13 +128,15 @@ impl<'a> Module<'a> {
     }
 }
 
+/// Propagates the pulses between all modules.
 #[derive(Debug)]
-struct CommunicationCoordinator<'a> {
+pub struct CommunicationCoordinator<'a> {
     modules: HashMap<&'a str, Module<'a>>,
 }
 
 impl<'a> CommunicationCoordinator<'a> {
-    fn initiate_communication(&mut self) -> (u64, u64) {
+    /// Pushes the button once, and returns the number of low and high pulses that were sent.
+    pub fn initiate_communication(&mut self) -> (u64, u64) {
         let mut num_low_pulses = 0;
         let mut num_high_pulses = 0;
         let mut signals_in_flight = VecDeque::from([Signal {
@@ -158,7 +162,7 @@ impl<'a> CommunicationCoordinator<'a> {
         (num_low_pulses, num_high_pulses)
     }
 
-    fn new(lines: Lines<'a>) -> Self {
+    pub fn new(lines: Lines<'a>) -> ParseResult<Self> {
         let mut modules = HashMap::new();
         let button_module = Module {
             output_modules: vec![BROADCASTER_ID],
@@ -174,7 +178,7 @@ impl<'a> CommunicationCoordinator<'a> {
         let mut all_senders = Vec::new();
         let mut all_sendees: Vec<Vec<_>> = Vec::new();
         for line in lines {
-            let (sender, sendees) = line.split_once(" -> ").unwrap();
+            let (sender, sendees) = parse::split_once(line, " -> ")?;
             all_senders.push(sender);
             all_sendees.push(sendees.split(", ").collect());
         }
@@ -182,8 +186,8 @@ impl<'a> CommunicationCoordinator<'a> {
         // Start by initializing the modules. We will subsequently update the modules with their
         // inbound/outbound modules.
         for sender in &mut all_senders {
-            if sender.starts_with('%') {
-                *sender = sender.strip_prefix('%').unwrap();
+            if let Some(flip_flop) = sender.strip_prefix('%') {
+                *sender = flip_flop;
                 modules.insert(
                     sender,
                     Module {
@@ -191,8 +195,8 @@ impl<'a> CommunicationCoordinator<'a> {
                         handler: ModuleHandler::FlipFlop { activated: false },
                     },
                 );
-            } else if sender.starts_with('&') {
-                *sender = sender.strip_prefix('&').unwrap();
+            } else if let Some(conjunction) = sender.strip_prefix('&') {
+                *sender = conjunction;
                 modules.insert(
                     sender,
                     Module {
@@ -202,12 +206,15 @@ impl<'a> CommunicationCoordinator<'a> {
                         },
                     },
                 );
+            } else if *sender != BROADCASTER_ID {
+                let expected = format!("a flip-flop, a conjunction or `{BROADCASTER_ID}`");
+                return Err(ParseError::new(sender, expected));
             } else {
-                // The only other case is the broadcaster module, which is already initialized.
+                // The broadcaster module is already initialized.
             }
         }
 
-        for (sender, sendees) in all_senders.iter().zip(all_sendees.into_iter()) {
+        for (sender, sendees) in all_senders.iter().zip(all_sendees) {
             for sendee in &sendees {
                 match modules.get_mut(sendee) {
                     Some(sendee_module) => {
@@ -237,36 +244,46 @@ impl<'a> CommunicationCoordinator<'a> {
             modules.get_mut(sender).unwrap().output_modules = sendees;
         }
 
-        CommunicationCoordinator { modules }
+        Ok(CommunicationCoordinator { modules })
     }
 }
 
 pub struct Day20Solver {}
 
 impl Solver for Day20Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day20/input.txt").unwrap();
-        let mut communication_coordinator = CommunicationCoordinator::new(file.lines());
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 20;
 
-        let (low_pulse_counts, high_pulse_counts): (Vec<_>, Vec<_>) = (0..1000)
-            .map(|_| communication_coordinator.initiate_communication())
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let mut communication_coordinator = CommunicationCoordinator::new(input.lines())?;
+
+        let (low_pulse_counts, high_pulse_counts): (Vec<_>, Vec<_>) = (1..=1000)
+            .map(|press| {
+                let (low_pulses, high_pulses) = communication_coordinator.initiate_communication();
+                trace!("press {press}: {low_pulses} low and {high_pulses} high pulses");
+                (low_pulses, high_pulses)
+            })
             .unzip();
         let sum_of_all_low_and_high_pulses_products =
             low_pulse_counts.iter().sum::<u64>() * high_pulse_counts.iter().sum::<u64>();
-        println!(
-            "The sum of all product of all low and high pulses after 1000 iterations is {}",
-            sum_of_all_low_and_high_pulses_products
-        );
+        Ok(sum_of_all_low_and_high_pulses_products.into())
     }
 
-    fn solve_part2() {
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
         // NOTE: This will not finish within a reasonable amount of time, per the README.
 
-        let file = std::fs::read_to_string("src/day20/input.txt").unwrap();
-        let mut communication_coordinator = CommunicationCoordinator::new(file.lines());
+        let mut communication_coordinator = CommunicationCoordinator::new(input.lines())?;
+        if !communication_coordinator.modules.contains_key(RX_ID) {
+            // There is no offending text to point at, so the error is at the end of the input.
+            return Err(ParseError::new(
+                &input[input.len()..],
+                format!("a module sending to `{RX_ID}`"),
+            ));
+        }
 
-        for i in 1.. {
-            communication_coordinator.initiate_communication();
+        for i in 1u64.. {
+            let (low_pulses, high_pulses) = communication_coordinator.initiate_communication();
+            trace!("press {i}: {low_pulses} low and {high_pulses} high pulses");
             match communication_coordinator
                 .modules
                 .get(RX_ID)
@@ -274,11 +291,47 @@ impl Solver for Day20Solver {
                 .handler
             {
                 ModuleHandler::Sink { received_low_pulse } if received_low_pulse => {
-                    println!("The rx module was activated after {} presses", i);
-                    break;
+                    return Ok(i.into());
                 }
                 _ => {}
             }
         }
+
+        unreachable!()
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        CommunicationCoordinator::new(input.lines())?;
+        Ok(())
+    }
+
+    fn terminates(part: Part) -> bool {
+        // Part 2 keeps pressing the button until rx gets a low pulse, see the README.
+        part != Part::Part2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_first_example() {
+        assert_eq!(
+            Day20Solver::solve_part1(include_str!("input-small1.txt"))
+                .unwrap()
+                .to_string(),
+            "32000000"
+        );
+    }
+
+    #[test]
+    fn part1_second_example() {
+        assert_eq!(
+            Day20Solver::solve_part1(include_str!("input-small2.txt"))
+                .unwrap()
+                .to_string(),
+            "11687500"
+        );
     }
 }
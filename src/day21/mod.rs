@@ -3,9 +3,10 @@ use std::{
     str::Lines,
 };
 
-use crate::solver::Solver;
-
-const GRID_SIZE: usize = 131;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Part, Solver};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum TileType {
@@ -27,13 +28,13 @@ impl TileType {
     }
 }
 
-impl From<char> for TileType {
-    fn from(value: char) -> Self {
+impl TileType {
+    fn from_char(value: char) -> Option<Self> {
         match value {
-            'S' => TileType::Start,
-            '.' => TileType::Garden,
-            '#' => TileType::Rock,
-            _ => unreachable!(),
+            'S' => Some(TileType::Start),
+            '.' => Some(TileType::Garden),
+            '#' => Some(TileType::Rock),
+            _ => None,
         }
     }
 }
@@ -42,68 +43,45 @@ impl From<char> for TileType {
 struct Tile {
     tile_type: TileType,
     // Iteration stepped at in x-translation and y-translation map.
-    stepped_at_iteration: HashMap<(i32, i32), usize>,
+    stepped_at_iteration: HashMap<Point, usize>,
 }
 
 #[derive(Debug)]
-struct Grid {
-    grid: Vec<Vec<Tile>>,
-    // (row, col, x-translation, y-translation)
-    start_position: (usize, usize, i32, i32),
+pub struct Garden {
+    grid: Grid<Tile>,
+    // The row and column of the start tile. When the map repeats itself infinitely, rows and
+    // columns beyond the map are those of its translated copies.
+    start_position: Point,
 }
 
-impl Grid {
-    fn explore_garden_plots(&mut self, steps: usize, infinite_map: bool) -> u32 {
+impl Garden {
+    /// Counts the garden plots reachable in exactly `steps` steps. If `infinite_map` is set,
+    /// the map repeats itself infinitely in every direction.
+    pub fn explore_garden_plots(&mut self, steps: usize, infinite_map: bool) -> u32 {
+        let num_rows = self.grid.num_rows() as i64;
+        let num_cols = self.grid.num_cols() as i64;
         let mut accessed_positions = vec![self.start_position];
         let mut new_accessed_positions = Vec::new();
 
         for i in 1..=steps {
-            for (row, col, x_translation, y_translation) in accessed_positions.drain(..) {
-                let mut next_positions_to_attempt = Vec::new();
-
-                if row > 0 {
-                    next_positions_to_attempt.push((row - 1, col, x_translation, y_translation));
-                } else if infinite_map {
-                    next_positions_to_attempt.push((
-                        GRID_SIZE - 1,
-                        col,
-                        x_translation - 1,
-                        y_translation,
-                    ));
-                }
-
-                if row < GRID_SIZE - 1 {
-                    next_positions_to_attempt.push((row + 1, col, x_translation, y_translation));
-                } else if infinite_map {
-                    next_positions_to_attempt.push((0, col, x_translation + 1, y_translation));
-                }
-
-                if col > 0 {
-                    next_positions_to_attempt.push((row, col - 1, x_translation, y_translation));
-                } else if infinite_map {
-                    next_positions_to_attempt.push((
-                        row,
-                        GRID_SIZE - 1,
-                        x_translation,
-                        y_translation - 1,
-                    ));
-                }
-
-                if col < GRID_SIZE - 1 {
-                    next_positions_to_attempt.push((row, col + 1, x_translation, y_translation));
-                } else if infinite_map {
-                    next_positions_to_attempt.push((row, 0, x_translation, y_translation + 1));
-                }
+            for point in accessed_positions.drain(..) {
+                for direction in Direction::ALL {
+                    let point = point + direction.offset();
+                    let translation = Point::new(
+                        point.row.div_euclid(num_rows),
+                        point.col.div_euclid(num_cols),
+                    );
+                    if !infinite_map && translation != Point::default() {
+                        continue;
+                    }
 
-                for (row, col, x_translation, y_translation) in next_positions_to_attempt {
-                    let tile = &mut self.grid[row][col];
+                    let position = self.grid.wrap(point);
+                    let tile = &mut self.grid[position];
                     if !tile.tile_type.can_step() {
                         continue;
                     }
 
-                    let stepped_at_iteration = tile
-                        .stepped_at_iteration
-                        .entry((x_translation, y_translation));
+                    let stepped_at_iteration = tile.stepped_at_iteration.entry(translation);
                     let add_new_access_position = match stepped_at_iteration {
                         Entry::Occupied(mut occupied_entry) => {
                             let iteration_stepped = occupied_entry.get_mut();
@@ -121,67 +99,95 @@ impl Grid {
                     };
 
                     if add_new_access_position {
-                        new_accessed_positions.push((row, col, x_translation, y_translation));
+                        new_accessed_positions.push(point);
                     }
                 }
             }
 
-            accessed_positions.extend(new_accessed_positions.drain(..));
+            accessed_positions.append(&mut new_accessed_positions);
         }
 
         accessed_positions.len() as u32
     }
 
-    fn new(lines: Lines) -> Self {
-        let mut start_position = (0, 0, 0, 0);
-        let grid = lines
-            .enumerate()
-            .map(|(row, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(col, character)| {
-                        let tile_type = TileType::from(character);
-                        if tile_type.is_start_tile() {
-                            start_position = (row, col, 0, 0);
-                        }
-                        Tile {
-                            tile_type,
-                            stepped_at_iteration: HashMap::from([((0, 0), 0)]),
-                        }
+    pub fn new(lines: Lines) -> ParseResult<Self> {
+        let mut start_position = None;
+        let mut last_line = "";
+        let grid = Grid::parse_rows(lines, |row, line| {
+            last_line = line;
+            parse::chars(line, "a tile", TileType::from_char)
+                .enumerate()
+                .map(|(col, tile_type)| {
+                    let tile_type = tile_type?;
+                    if tile_type.is_start_tile() {
+                        start_position = Some(Point::new(row as i64, col as i64));
+                    }
+                    Ok(Tile {
+                        tile_type,
+                        stepped_at_iteration: HashMap::from([(Point::default(), 0)]),
                     })
-                    .collect()
-            })
-            .collect();
+                })
+                .collect()
+        })?;
 
-        Self {
+        let start_position = start_position
+            .ok_or_else(|| ParseError::new(&last_line[last_line.len()..], "a start tile"))?;
+        Ok(Self {
             grid,
             start_position,
-        }
+        })
     }
 }
 
 pub struct Day21Solver {}
 
 impl Solver for Day21Solver {
-    fn solve_part1() {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 21;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
         let steps = 64;
-        let file = std::fs::read_to_string("src/day21/input.txt").unwrap();
-        let mut grid = Grid::new(file.lines());
-        let explored_garden_plots = grid.explore_garden_plots(steps, false);
-        println!(
-            "The elf can reach {} garden plots in {} steps.",
-            explored_garden_plots, steps
-        );
+        let mut garden = Garden::new(input.lines())?;
+        Ok(garden.explore_garden_plots(steps, false).into())
     }
 
-    fn solve_part2() {
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
         let steps = 5000;
-        let file = std::fs::read_to_string("src/day21/input.txt").unwrap();
-        let mut grid = Grid::new(file.lines());
-        let explored_garden_plots = grid.explore_garden_plots(steps, true);
-        println!(
-            "The elf can reach {} garden plots in {} steps.",
-            explored_garden_plots, steps
-        );
+        let mut garden = Garden::new(input.lines())?;
+        Ok(garden.explore_garden_plots(steps, true).into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Garden::new(input.lines())?;
+        Ok(())
+    }
+
+    fn terminates(part: Part) -> bool {
+        // Part 2 explores the infinite map step by step, see the README.
+        part != Part::Part2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        // The statement gives the answer after 6 steps rather than 64.
+        let mut garden = Garden::new(EXAMPLE.lines()).unwrap();
+        assert_eq!(garden.explore_garden_plots(6, false), 16);
+    }
+
+    #[test]
+    fn part2_example() {
+        // The statement gives the answers after a few steps on the infinite map, which are quick
+        // to explore unlike 5000.
+        for (steps, garden_plots) in [(6, 16), (10, 50), (50, 1594), (100, 6536)] {
+            let mut garden = Garden::new(EXAMPLE.lines()).unwrap();
+            assert_eq!(garden.explore_garden_plots(steps, true), garden_plots);
+        }
     }
 }
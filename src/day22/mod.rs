@@ -4,14 +4,13 @@ use std::{
     str::Lines,
 };
 
-use crate::solver::Solver;
-
-const X_Y_GRID_SIZE: usize = 10;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 // Note that we order bricks first by their z_start, and then by their z_end.
 // The rest of the fields don't impact the ordering.
 #[derive(Debug, PartialEq, Eq)]
-struct Brick {
+pub struct Brick {
     x_start: u8,
     x_end: u8,
     y_start: u8,
@@ -57,12 +56,26 @@ struct BrickRef {
     max_z: usize,
 }
 
-#[derive(Default)]
 struct XYGrid {
-    zs: [[BrickRef; X_Y_GRID_SIZE]; X_Y_GRID_SIZE],
+    zs: Vec<Vec<BrickRef>>,
 }
 
 impl XYGrid {
+    // Creates a grid large enough for the x and y coordinates of all the bricks.
+    fn new(bricks: &[Brick]) -> Self {
+        let x_size = bricks.iter().map(|brick| brick.x_end as usize + 1).max();
+        let y_size = bricks.iter().map(|brick| brick.y_end as usize + 1).max();
+        let zs = (0..x_size.unwrap_or(0))
+            .map(|_| {
+                (0..y_size.unwrap_or(0))
+                    .map(|_| BrickRef::default())
+                    .collect()
+            })
+            .collect();
+
+        Self { zs }
+    }
+
     fn brick_fall_z(&self, brick_xy_coordinates: &[(usize, usize)]) -> usize {
         let mut max_z = 0;
 
@@ -76,8 +89,7 @@ impl XYGrid {
     // Note that this returns the updated input vector, which we drained and moved to the output.
     // The reason why is to deflect the borrowchecker.
     fn free_fall(&mut self, mut bricks: Vec<Brick>) -> Vec<Brick> {
-        let mut fallen_bricks = Vec::<Brick>::new();
-        fallen_bricks.reserve(bricks.len());
+        let mut fallen_bricks = Vec::<Brick>::with_capacity(bricks.len());
 
         for (brick_index, mut brick) in bricks.drain(..).enumerate() {
             let brick_xy_coordinates = brick.get_xy_coordinates();
@@ -87,13 +99,14 @@ impl XYGrid {
 
             for (x, y) in brick_xy_coordinates {
                 let under_brick_ref = &mut self.zs[x][y];
-                if under_brick_ref.max_z == brick_fall_z && under_brick_ref.index.is_some() {
-                    let under_brick_index = under_brick_ref.index.unwrap();
-
-                    fallen_bricks[under_brick_index]
-                        .bricks_on_top
-                        .insert(brick_index);
-                    brick.bricks_underneath.insert(under_brick_index);
+                match under_brick_ref.index {
+                    Some(under_brick_index) if under_brick_ref.max_z == brick_fall_z => {
+                        fallen_bricks[under_brick_index]
+                            .bricks_on_top
+                            .insert(brick_index);
+                        brick.bricks_underneath.insert(under_brick_index);
+                    }
+                    _ => {}
                 }
 
                 under_brick_ref.index = Some(brick_index);
@@ -113,12 +126,12 @@ impl XYGrid {
 // No bricks in diagonal: only varies in x, y, or z (or none, if it's a single cube).
 // Start is smaller or equal to end, i.e. no bricks upside down.
 // Lowest z-index possible is 1.
-// All x and y coordinates are within [0-9].
 
 pub struct Day22Solver {}
 
 impl Day22Solver {
-    fn count_safely_disintegrable_bricks(bricks: &[Brick]) -> usize {
+    /// Counts the bricks that could be disintegrated without making any other brick fall.
+    pub fn count_safely_disintegrable_bricks(bricks: &[Brick]) -> usize {
         let mut count = 0;
 
         'next_brick: for brick in bricks {
@@ -138,7 +151,8 @@ impl Day22Solver {
         count
     }
 
-    fn count_sum_of_other_fallable_bricks(bricks: &[Brick]) -> usize {
+    /// Sums, for each brick, the number of other bricks that would fall if it was disintegrated.
+    pub fn count_sum_of_other_fallable_bricks(bricks: &[Brick]) -> usize {
         let mut count = 0;
 
         for i in 0..bricks.len() {
@@ -171,27 +185,35 @@ impl Day22Solver {
         count
     }
 
-    fn free_fall(bricks: Vec<Brick>) -> Vec<Brick> {
-        let mut x_y_grid = XYGrid::default();
+    /// Lets the sorted bricks fall until they settle.
+    pub fn free_fall(bricks: Vec<Brick>) -> Vec<Brick> {
+        let mut x_y_grid = XYGrid::new(&bricks);
         x_y_grid.free_fall(bricks)
     }
 
-    fn parse_and_sort_bricks(lines: Lines) -> Vec<Brick> {
+    /// Parses the bricks, sorted from lowest to highest.
+    pub fn parse_and_sort_bricks(lines: Lines) -> ParseResult<Vec<Brick>> {
         let mut bricks = Vec::new();
         for line in lines {
-            let (start_coordinate, end_coordinate) = line.split_once('~').unwrap();
-
-            let (x_start, y_and_z_start) = start_coordinate.split_once(',').unwrap();
-            let (y_start, z_start) = y_and_z_start.split_once(',').unwrap();
-            let x_start = x_start.parse().unwrap();
-            let y_start = y_start.parse().unwrap();
-            let z_start = z_start.parse().unwrap();
-
-            let (x_end, y_and_z_end) = end_coordinate.split_once(',').unwrap();
-            let (y_end, z_end) = y_and_z_end.split_once(',').unwrap();
-            let x_end = x_end.parse().unwrap();
-            let y_end = y_end.parse().unwrap();
-            let z_end = z_end.parse().unwrap();
+            let (start_coordinate, end_coordinate) = parse::split_once(line, "~")?;
+
+            let (x_start, y_and_z_start) = parse::split_once(start_coordinate, ",")?;
+            let (y_start, z_start) = parse::split_once(y_and_z_start, ",")?;
+            let x_start = parse::number(x_start)?;
+            let y_start = parse::number(y_start)?;
+            let z_start = parse::number(z_start)?;
+
+            let (x_end, y_and_z_end) = parse::split_once(end_coordinate, ",")?;
+            let (y_end, z_end) = parse::split_once(y_and_z_end, ",")?;
+            let x_end = parse::number(x_end)?;
+            let y_end = parse::number(y_end)?;
+            let z_end = parse::number(z_end)?;
+            if x_start > x_end || y_start > y_end || z_start > z_end {
+                return Err(ParseError::new(
+                    end_coordinate,
+                    "an end coordinate at or past the start one on every axis",
+                ));
+            }
 
             bricks.push(Brick {
                 x_start,
@@ -206,28 +228,55 @@ impl Day22Solver {
         }
 
         bricks.sort();
-        bricks
+        Ok(bricks)
     }
 }
 
 impl Solver for Day22Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day22/input.txt").unwrap();
-        let bricks = Self::parse_and_sort_bricks(file.lines());
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 22;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let bricks = Self::parse_and_sort_bricks(input.lines())?;
         let fallen_bricks = Self::free_fall(bricks);
-        println!(
-            "The number of bricks that could safely be disintegrated is {}",
-            Self::count_safely_disintegrable_bricks(&fallen_bricks)
-        );
+        Ok(Self::count_safely_disintegrable_bricks(&fallen_bricks).into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day22/input.txt").unwrap();
-        let bricks = Self::parse_and_sort_bricks(file.lines());
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let bricks = Self::parse_and_sort_bricks(input.lines())?;
         let fallen_bricks = Self::free_fall(bricks);
-        println!(
-            "The total number of bricks that would fall in chain is {}",
-            Self::count_sum_of_other_fallable_bricks(&fallen_bricks)
-        );
+        Ok(Self::count_sum_of_other_fallable_bricks(&fallen_bricks).into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Self::parse_and_sort_bricks(input.lines())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::Part;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day22Solver::solve_part1(EXAMPLE).unwrap().to_string(), "5");
+    }
+
+    #[test]
+    fn reversed_brick_is_invalid() {
+        for input in ["0,0,5~0,0,1", "5,0,1~0,0,1", "0,5,1~0,0,1"] {
+            let error = Day22Solver::solve(Part::Part1, input).unwrap_err();
+            assert_eq!((error.line, error.column), (1, 7));
+            assert_eq!(error.text, "0,0,1");
+        }
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day22Solver::solve_part2(EXAMPLE).unwrap().to_string(), "7");
     }
 }
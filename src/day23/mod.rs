@@ -1,13 +1,11 @@
-use hashbrown::{HashMap, HashSet};
 use std::str::Lines;
 
-use crate::solver::Solver;
-
-const GRID_SIZE: usize = 141;
-const START_POSITION: RowCol = (0, 1);
-const END_POSITION: RowCol = (GRID_SIZE - 1, GRID_SIZE - 2);
-
-type RowCol = (usize, usize);
+use crate::geometry::{Direction, Position};
+use crate::graph::{AdjacencyList, Graph, IndexedGraph};
+use crate::grid::Grid;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
+use crate::trace;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum Tile {
@@ -25,250 +23,192 @@ impl Tile {
     }
 }
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
+impl Tile {
+    fn from_char(value: char) -> Option<Self> {
+        let tile = match value {
             '.' => Self::Path,
             '#' => Self::Forest,
             '>' => Self::RightSlope,
             'v' => Self::DownSlope,
             '<' => Self::LeftSlope,
             '^' => Self::UpSlope,
-            _ => unreachable!(),
-        }
+            _ => return None,
+        };
+
+        Some(tile)
     }
 }
 
-struct Grid {
-    grid: Vec<Vec<Tile>>,
+pub struct TrailMap {
+    grid: Grid<Tile>,
+    // The only path tiles of the first and last rows.
+    start_position: Position,
+    end_position: Position,
 }
 
-impl Grid {
-    fn new(lines: Lines) -> Self {
-        let grid = lines
-            .map(|line| line.chars().map(Tile::from).collect())
-            .collect();
-
-        Self { grid }
+impl TrailMap {
+    pub fn new(lines: Lines) -> ParseResult<Self> {
+        let mut rows = Vec::new();
+        let grid = Grid::parse_rows(lines, |_, line| {
+            rows.push(line);
+            parse::chars(line, "a tile", Tile::from_char).collect()
+        })?;
+
+        let find_path = |row: usize| {
+            let line = rows.get(row).copied().unwrap_or_default();
+            (row < grid.num_rows())
+                .then(|| grid.row(row).iter().position(|tile| *tile == Tile::Path))
+                .flatten()
+                .map(|col| Position::new(row, col))
+                .ok_or_else(|| ParseError::new(line, "a row with a path"))
+        };
+        let start_position = find_path(0)?;
+        let end_position = find_path(grid.num_rows().saturating_sub(1))?;
+
+        Ok(Self {
+            grid,
+            start_position,
+            end_position,
+        })
     }
 
-    fn compact(&self, slippery_slope: bool) -> CompactGrid {
-        let mut compact_grid = CompactGrid::default();
-        compact_grid.nodes.insert(START_POSITION, Vec::new());
-        compact_grid.nodes.insert(END_POSITION, Vec::new());
-
-        for i in 0..GRID_SIZE {
-            for j in 0..GRID_SIZE {
-                if (i, j) == START_POSITION || (i, j) == END_POSITION {
-                    continue;
-                }
-
-                let tile = &self.grid[i][j];
-                if tile.is_forest() {
-                    continue;
-                }
-
-                // Ignore slippery slope for this part of the compaction. We just want to know how many
-                // neighbours there are for non-forest tiles. If it has more than 2 neighbours, then it
-                // is a 3- or 4-way crossroad. This will become a node in our graph. Otherwise, it is a point
-                // that will get compacted away.
-                let neighbours = self.get_next_positions((i, j), *tile, false);
-                let valid_neighbours: Vec<_> = neighbours
-                    .into_iter()
-                    .filter(|(new_row, new_col)| !self.grid[*new_row][*new_col].is_forest())
-                    .collect();
-                if valid_neighbours.len() > 2 {
-                    compact_grid.nodes.insert((i, j), Vec::new());
-                }
-            }
-        }
-
-        let nodes: Vec<_> = compact_grid.nodes.keys().cloned().collect();
-        for node in nodes {
-            let (row, col) = node;
-            let paths = self.get_next_positions(node, self.grid[row][col], slippery_slope);
-            let valid_paths: Vec<_> = paths
-                .into_iter()
-                .filter(|(new_row, new_col)| !self.grid[*new_row][*new_col].is_forest())
-                .collect();
-
-            'next_path: for (mut new_row, mut new_col) in valid_paths {
-                let mut visited_nodes: HashSet<RowCol> = HashSet::from([node, (new_row, new_col)]);
-                let mut weight = 1;
-
-                'next_position: loop {
-                    for (tentative_row, tentative_col) in self.get_next_positions(
-                        (new_row, new_col),
-                        self.grid[new_row][new_col],
-                        slippery_slope,
-                    ) {
-                        if self.grid[tentative_row][tentative_col].is_forest()
-                            || visited_nodes.contains(&(tentative_row, tentative_col))
-                        {
-                            continue;
-                        }
-
-                        weight += 1;
-                        new_row = tentative_row;
-                        new_col = tentative_col;
-                        visited_nodes.insert((new_row, new_col));
-
-                        // We found a path that leads to a node in the graph (but not the origin node of the path).
-                        // Let's add a directed edge from the origin node to the found node, and try a new path.
-                        if compact_grid.nodes.contains_key(&(new_row, new_col)) {
-                            compact_grid.nodes.get_mut(&node).unwrap().push(Edge {
-                                destination: (new_row, new_col),
-                                weight,
-                            });
-
-                            continue 'next_path;
-                        }
-
-                        continue 'next_position;
-                    }
+    /// Compacts the grid into a graph of its crossroads, weighted by the length of the paths
+    /// between them.
+    pub fn compact(&self, slippery_slope: bool) -> CompactGrid {
+        // Ignore slippery slope for this part of the compaction. We just want to know how many
+        // neighbours there are for non-forest tiles. If it has more than 2 neighbours, then it
+        // is a 3- or 4-way crossroad. This will become a node in our graph. Otherwise, it is a point
+        // that will get compacted away.
+        let all_trails = Trails {
+            map: self,
+            slippery_slope: false,
+        };
+        let crossroads = self.grid.iter().filter_map(|(position, tile)| {
+            (!tile.is_forest() && all_trails.edges(position).count() > 2).then_some(position)
+        });
+        let nodes: Vec<_> = [self.start_position, self.end_position]
+            .into_iter()
+            .chain(crossroads)
+            .collect();
 
-                    // If we tried all positions orthogonal to the current one and none of them are valid,
-                    // the path that is being explored leads to no where. Drop it, and move to a new path
-                    // to explore.
-                    continue 'next_path;
-                }
-            }
+        let trails = Trails {
+            map: self,
+            slippery_slope,
+        };
+        let graph = trails.contract(&nodes);
+        trace!(
+            "compacted {} tiles into {} nodes",
+            self.grid
+                .iter()
+                .filter(|(_, tile)| !tile.is_forest())
+                .count(),
+            graph.num_nodes()
+        );
+        CompactGrid {
+            graph,
+            start_node: 0,
+            end_node: 1,
         }
-
-        compact_grid
     }
 
     fn get_next_positions(
         &self,
-        current_position: RowCol,
+        current_position: Position,
         current_tile: Tile,
         slippery_slope: bool,
-    ) -> Vec<RowCol> {
-        let mut next_positions = Vec::new();
-        let (row, col) = current_position;
-
-        match current_tile {
+    ) -> Vec<Position> {
+        let slope = match current_tile {
             Tile::Forest => unreachable!(),
-            Tile::RightSlope if slippery_slope => {
-                if col < GRID_SIZE - 1 {
-                    next_positions.push((row, col + 1));
-                }
-            }
-            Tile::DownSlope if slippery_slope => {
-                if row < GRID_SIZE - 1 {
-                    next_positions.push((row + 1, col));
-                }
-            }
-            Tile::LeftSlope if slippery_slope => {
-                if col > 0 {
-                    next_positions.push((row, col - 1));
-                }
-            }
-            Tile::UpSlope if slippery_slope => {
-                if row > 0 {
-                    next_positions.push((row - 1, col));
-                }
-            }
-            _ => {
-                // Optimization. This intersection must only direct
-                // down, as this is the only path to the end position.
-                // During compaction, this will have the effect of ignoring
-                // this intersection altogether.
-                if row == 135 && col == 133 {
-                    next_positions.push((row + 1, col));
-                    return next_positions;
-                }
-                if col < GRID_SIZE - 1 {
-                    next_positions.push((row, col + 1));
-                }
-                if row < GRID_SIZE - 1 {
-                    next_positions.push((row + 1, col));
-                }
-                if col > 0 {
-                    next_positions.push((row, col - 1));
-                }
-                if row > 0 {
-                    next_positions.push((row - 1, col));
-                }
-            }
-        }
-
-        next_positions
+            Tile::RightSlope if slippery_slope => Direction::Right,
+            Tile::DownSlope if slippery_slope => Direction::Down,
+            Tile::LeftSlope if slippery_slope => Direction::Left,
+            Tile::UpSlope if slippery_slope => Direction::Up,
+            _ => return self.grid.neighbours4(current_position).collect(),
+        };
+
+        self.grid
+            .step(current_position, slope)
+            .into_iter()
+            .collect()
     }
 }
 
-#[derive(Debug)]
-struct Edge {
-    destination: RowCol,
-    weight: usize,
+/// The trails of the map, as a graph of the tiles that can be stepped on.
+struct Trails<'a> {
+    map: &'a TrailMap,
+    slippery_slope: bool,
+}
+
+impl Graph for Trails<'_> {
+    type Node = Position;
+
+    fn edges(&self, position: Position) -> impl Iterator<Item = (Position, u64)> {
+        self.map
+            .get_next_positions(position, self.map.grid[position], self.slippery_slope)
+            .into_iter()
+            .filter(|next_position| !self.map.grid[*next_position].is_forest())
+            .map(|next_position| (next_position, 1))
+    }
 }
 
-#[derive(Default, Debug)]
-struct CompactGrid {
-    nodes: HashMap<RowCol, Vec<Edge>>,
+#[derive(Debug)]
+pub struct CompactGrid {
+    graph: AdjacencyList,
+    start_node: usize,
+    end_node: usize,
 }
 
 impl CompactGrid {
-    fn find_longest_path(&self) -> usize {
-        let mut visited_positions = [[false; GRID_SIZE]; GRID_SIZE];
-        self.recurisvely_find_longest_path(START_POSITION, &mut visited_positions)
+    /// Finds the longest hike from the start to the end, without stepping twice on a tile.
+    pub fn find_longest_path(&self) -> u64 {
+        self.graph
+            .longest_path(self.start_node, self.end_node)
+            .unwrap_or_default()
     }
+}
 
-    fn recurisvely_find_longest_path(
-        &self,
-        position: RowCol,
-        visited_positions: &mut [[bool; GRID_SIZE]; GRID_SIZE],
-    ) -> usize {
-        let mut longest_path = 0;
-        if position == END_POSITION {
-            return longest_path;
-        }
+pub struct Day23Solver {}
 
-        let (row, col) = position;
-        visited_positions[row][col] = true;
-        let current_edges = self.nodes.get(&position).unwrap();
-        for current_edge in current_edges {
-            let (destination_row, destination_col) = current_edge.destination;
-            if visited_positions[destination_row][destination_col] {
-                continue;
-            }
+impl Solver for Day23Solver {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 23;
 
-            longest_path = longest_path.max(
-                current_edge.weight
-                    + self
-                        .recurisvely_find_longest_path(current_edge.destination, visited_positions),
-            );
-        }
-        visited_positions[row][col] = false;
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let slippery_slope = true;
+        let map = TrailMap::new(input.lines())?;
+        let compact_grid = map.compact(slippery_slope);
+        Ok(compact_grid.find_longest_path().into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let slippery_slope = false;
+        let map = TrailMap::new(input.lines())?;
+        let compact_grid = map.compact(slippery_slope);
+        Ok(compact_grid.find_longest_path().into())
+    }
 
-        longest_path
+    fn parse(input: &str) -> ParseResult<()> {
+        TrailMap::new(input.lines())?;
+        Ok(())
     }
 }
 
-pub struct Day23Solver {}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl Day23Solver {}
+    const EXAMPLE: &str = include_str!("input-small.txt");
 
-impl Solver for Day23Solver {
-    fn solve_part1() {
-        let slippery_slope = true;
-        let file = std::fs::read_to_string("src/day23/input.txt").unwrap();
-        let grid = Grid::new(file.lines());
-        let compact_grid = grid.compact(slippery_slope);
-        println!(
-            "The longest hike considering slopes is {:?}",
-            compact_grid.find_longest_path()
-        );
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day23Solver::solve_part1(EXAMPLE).unwrap().to_string(), "94");
     }
 
-    fn solve_part2() {
-        let slippery_slope = false;
-        let file = std::fs::read_to_string("src/day23/input.txt").unwrap();
-        let grid = Grid::new(file.lines());
-        let compact_grid = grid.compact(slippery_slope);
-        println!(
-            "The longest hike ignoring slopes is {:?}",
-            compact_grid.find_longest_path()
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day23Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "154"
         );
     }
 }
@@ -1,6 +1,7 @@
 use std::{ops::RangeInclusive, str::Lines};
 
-use crate::solver::Solver;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 const MIN_X: f64 = 200_000_000_000_000.0;
 const MIN_Y: f64 = 200_000_000_000_000.0;
@@ -8,17 +9,18 @@ const MAX_X: f64 = 400_000_000_000_000.0;
 const MAX_Y: f64 = 400_000_000_000_000.0;
 
 #[derive(Debug)]
-struct Hail {
-    x: f64,
-    y: f64,
-    z: f64,
-    vx: f64,
-    vy: f64,
-    vz: f64,
+pub struct Hail {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub vx: f64,
+    pub vy: f64,
+    pub vz: f64,
 }
 
 impl Hail {
-    fn intersect_at_xy(&self, b: &Self) -> Option<(f64, f64)> {
+    /// Returns where both hailstones' paths cross in the future, ignoring the Z axis.
+    pub fn intersect_at_xy(&self, b: &Self) -> Option<(f64, f64)> {
         // The following equations were derived on a whiteboard. There are two variables
         // to solve, thus two equations are needed. With substitution, we end up finding
         // the time at which both hail stones intersect, which we can then use to find
@@ -53,43 +55,48 @@ impl Hail {
     }
 }
 
-impl From<&str> for Hail {
-    fn from(value: &str) -> Self {
-        let (coordinates, velocities) = value.split_once(" @ ").unwrap();
-        let coordinates: Vec<_> = coordinates.split(", ").collect();
-        let velocities: Vec<_> = velocities.split(", ").collect();
-
-        let x = coordinates[0].parse().unwrap();
-        let y = coordinates[1].parse().unwrap();
-        let z = coordinates[2].parse().unwrap();
-        let vx = velocities[0].parse().unwrap();
-        let vy = velocities[1].parse().unwrap();
-        let vz = velocities[2].parse().unwrap();
+impl Hail {
+    /// Parses the hailstone's position and velocity, separated by `@`.
+    pub fn parse(value: &str) -> ParseResult<Self> {
+        let (coordinates, velocities) = parse::split_once(value, "@")?;
+        let [x, y, z] = Self::parse_triplet(coordinates)?;
+        let [vx, vy, vz] = Self::parse_triplet(velocities)?;
 
-        Self {
+        Ok(Self {
             x,
             y,
             z,
             vx,
             vy,
             vz,
-        }
+        })
+    }
+
+    fn parse_triplet(value: &str) -> ParseResult<[f64; 3]> {
+        let triplet = value
+            .split(',')
+            .map(|number| parse::number(number.trim()))
+            .collect::<ParseResult<Vec<_>>>()?;
+        triplet
+            .try_into()
+            .map_err(|_| ParseError::new(value, "3 comma-separated numbers"))
     }
 }
 
 #[derive(Debug)]
-struct HailCollider {
+pub struct HailCollider {
     hails: Vec<Hail>,
 }
 
 impl HailCollider {
-    fn new(lines: Lines) -> Self {
-        let hails = lines.map(Hail::from).collect();
+    pub fn new(lines: Lines) -> ParseResult<Self> {
+        let hails = lines.map(Hail::parse).collect::<ParseResult<_>>()?;
 
-        Self { hails }
+        Ok(Self { hails })
     }
 
-    fn count_xy_trajectories_intersections(
+    /// Counts the pairs of hailstones whose paths cross within the test area.
+    pub fn count_xy_trajectories_intersections(
         &self,
         intersection_conditions: IntersectionConditions,
     ) -> u32 {
@@ -117,7 +124,10 @@ impl HailCollider {
         count
     }
 
-    fn find_magic_rock_coordinates_sum(&self) -> i64 {
+    /// Finds the rock's throw that hits all hailstones, and returns the sum of its initial
+    /// coordinates. Returns `None` if there are fewer than 3 hailstones, or if the paths of the
+    /// first 3 aren't independent.
+    pub fn find_magic_rock_coordinates_sum(&self) -> Option<i64> {
         // Just like for part 1, most of the work was done on a white board. The main goal was to
         // derive a system of linear equations, which was especially tricky because the equations
         // are themselves non-linear. I had to apply vectorial and linear algebra
@@ -140,9 +150,9 @@ impl HailCollider {
         // our system linear, so we actually need the data from 3 stones: the 2nd one is merged in the
         // 1st, and the 3rd is merged into the 1st. Thus, we end up with 6 equations. One requirement is that
         // each selected stone must be linearly independent, which the first 3 in our dataset are.
-        let a = &self.hails[0];
-        let b = &self.hails[1];
-        let c = &self.hails[2];
+        let [a, b, c, ..] = self.hails.as_slice() else {
+            return None;
+        };
 
         #[rustfmt::skip]
         let a_matrix = nalgebra::Matrix6::new(
@@ -164,41 +174,71 @@ impl HailCollider {
 
         // X is a 6-long vector. It contains, in order, the position of the rock (x, y, and z),
         // and its velocity (x, y, and z).
-        let x = a_matrix
-            .lu()
-            .solve(&b_vector)
-            .expect("Linear resolution failed");
+        let x = a_matrix.lu().solve(&b_vector)?;
 
-        (x[0] + x[1] + x[2]).round() as i64
+        Some((x[0] + x[1] + x[2]).round() as i64)
     }
 }
 
-struct IntersectionConditions {
-    x: RangeInclusive<f64>,
-    y: RangeInclusive<f64>,
+pub struct IntersectionConditions {
+    pub x: RangeInclusive<f64>,
+    pub y: RangeInclusive<f64>,
 }
 
 pub struct Day24Solver {}
 
 impl Solver for Day24Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day24/input.txt").unwrap();
-        let hail_collider = HailCollider::new(file.lines());
-        let num_intersections =
-            hail_collider.count_xy_trajectories_intersections(IntersectionConditions {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 24;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let hail_collider = HailCollider::new(input.lines())?;
+        Ok(hail_collider
+            .count_xy_trajectories_intersections(IntersectionConditions {
                 x: MIN_X..=MAX_X,
                 y: MIN_Y..=MAX_Y,
-            });
+            })
+            .into())
+    }
 
-        println!("There were {num_intersections} intersections for the given input and the given time/space constraints.");
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let hail_collider = HailCollider::new(input.lines())?;
+        let sum = hail_collider
+            .find_magic_rock_coordinates_sum()
+            .ok_or_else(|| {
+                // Point at the 3rd hailstone, or where it is missing.
+                let third_hail = input.lines().nth(2).unwrap_or(&input[input.len()..]);
+                ParseError::new(third_hail, "3 hailstones whose paths are independent")
+            })?;
+
+        Ok(sum.into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day24/input.txt").unwrap();
-        let hail_collider = HailCollider::new(file.lines());
-        println!(
-            "The sum of the coordinates of the magic rock hitting all hail stones is {}",
-            hail_collider.find_magic_rock_coordinates_sum()
-        );
+    fn parse(input: &str) -> ParseResult<()> {
+        HailCollider::new(input.lines())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        let hail_collider = HailCollider::new(EXAMPLE.lines()).unwrap();
+        let intersections =
+            hail_collider.count_xy_trajectories_intersections(IntersectionConditions {
+                x: 7.0..=27.0,
+                y: 7.0..=27.0,
+            });
+        assert_eq!(intersections, 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day24Solver::solve_part2(EXAMPLE).unwrap().to_string(), "47");
     }
 }
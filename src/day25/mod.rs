@@ -1,160 +1,112 @@
 /// Solution heavily inspired by
 /// https://www.reddit.com/r/adventofcode/comments/18qbsxs/comment/kfoynua/.
-use hashbrown::{HashMap, HashSet};
-use std::{collections::VecDeque, str::Lines};
+use hashbrown::HashMap;
+use std::str::Lines;
 
-use crate::solver::Solver;
+use crate::graph::{AdjacencyList, Graph, IndexedGraph};
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 #[derive(Debug)]
-struct Components {
-    components: Vec<Vec<usize>>,
+pub struct Components {
+    components: AdjacencyList,
 }
 
 impl Components {
-    fn new(lines: Lines) -> Self {
+    pub fn new(lines: Lines) -> ParseResult<Self> {
         let mut name_to_id_mapping = HashMap::new();
-        let mut components = Vec::new();
+        let mut components = AdjacencyList::new();
 
         for line in lines {
-            let (src, dsts) = line.split_once(": ").unwrap();
-            let src_id = *name_to_id_mapping.entry(src).or_insert_with(|| {
-                let id = components.len();
-                components.push(Vec::new());
-                id
-            });
+            let (src, dsts) = parse::split_once(line, ": ")?;
+            let src_id = *name_to_id_mapping
+                .entry(src)
+                .or_insert_with(|| components.add_node());
 
             dsts.split_whitespace().for_each(|dst| {
-                let dst_id = *name_to_id_mapping.entry(dst).or_insert_with(|| {
-                    let id = components.len();
-                    components.push(Vec::new());
-                    id
-                });
-
-                components[src_id].push(dst_id);
-                components[dst_id].push(src_id);
+                let dst_id = *name_to_id_mapping
+                    .entry(dst)
+                    .or_insert_with(|| components.add_node());
+
+                components.add_undirected_edge(src_id, dst_id, 1);
             });
         }
 
-        Self { components }
+        Ok(Self { components })
     }
 
-    fn find_two_partition_lengths_in_3_cuts(&self) -> (usize, usize) {
+    /// Finds the sizes of the two groups obtained by cutting 3 wires, or `None` if the
+    /// components can't be split in two that way.
+    pub fn find_two_partition_lengths_in_3_cuts(&self) -> Option<(usize, usize)> {
+        if self.components.num_nodes() == 0 {
+            return None;
+        }
+
+        // The nodes furthest apart are most likely on each side of the cut.
         let arbitrary_node = 0;
         let node_a = self.find_furthest_node(arbitrary_node);
         let node_b = self.find_furthest_node(node_a);
-
-        // Apply 3 cuts. All 3 cuts should not partition the graph.
-        let mut edges_in_visited_paths = HashSet::new();
-        for _ in 0..3 {
-            assert!(self
-                .find_path_or_return_partition_size(node_a, node_b, &mut edges_in_visited_paths)
-                .is_none());
+        if node_a == node_b {
+            return None;
         }
 
-        // The 4th and last cut should partition the graph.
-        let partition_1_size = self
-            .find_path_or_return_partition_size(node_a, node_b, &mut edges_in_visited_paths)
-            .expect("The 3 previous paths did not partition the graph");
+        let cut = self.components.min_cut(node_a, node_b);
+        if cut.size != 3 {
+            return None;
+        }
 
-        (partition_1_size, self.components.len() - partition_1_size)
+        let partition_1_size = cut.source_side.len();
+        Some((
+            partition_1_size,
+            self.components.num_nodes() - partition_1_size,
+        ))
     }
 
     fn find_furthest_node(&self, from: usize) -> usize {
-        let mut visited = vec![false; self.components.len()];
-        let mut to_visits = VecDeque::from([from]);
-        let mut last = from;
-
-        while let Some(to_visit) = to_visits.pop_front() {
-            if visited[to_visit] {
-                continue;
-            }
-
-            last = to_visit;
-            visited[last] = true;
-
-            for neighbour in &self.components[to_visit] {
-                if visited[*neighbour] {
-                    continue;
-                }
-
-                to_visits.push_back(*neighbour);
-            }
-        }
-
+        let (last, _) = *self.components.breadth_first_search(from).last().unwrap();
         last
     }
-
-    fn find_path_or_return_partition_size(
-        &self,
-        node_a: usize,
-        node_b: usize,
-        edges_in_visited_paths: &mut HashSet<(usize, usize)>,
-    ) -> Option<usize> {
-        let mut visited_nodes = vec![false; self.components.len()];
-        // Pairs of next node with the index of the edge that linked to them. The latter will be
-        // important when we walk the path back. We start with MAX as a sentinel value.
-        let mut to_visits = VecDeque::from([(node_a, usize::MAX)]);
-        // Pairs of edges and the index of the previous edge that linked to it.
-        let mut prevs = Vec::new();
-        // Count of the connected nodes from `node_a`.
-        let mut count = 0;
-
-        while let Some((to_visit, mut prev)) = to_visits.pop_front() {
-            if visited_nodes[to_visit] {
-                continue;
-            }
-
-            count += 1;
-            visited_nodes[to_visit] = true;
-
-            if to_visit == node_b {
-                // Walk the path back.
-                while prev != usize::MAX {
-                    let (edge, prev_prev) = prevs[prev];
-                    edges_in_visited_paths.insert(edge);
-                    prev = prev_prev;
-                }
-
-                return None;
-            }
-
-            for neighbour in &self.components[to_visit] {
-                // We order the edge to have a consistent representation of an edge (this is an undirected graph).
-                let edge = if to_visit < *neighbour {
-                    (to_visit, *neighbour)
-                } else {
-                    (*neighbour, to_visit)
-                };
-
-                if visited_nodes[*neighbour] || edges_in_visited_paths.contains(&edge) {
-                    continue;
-                }
-
-                to_visits.push_back((*neighbour, prevs.len()));
-                prevs.push((edge, prev));
-            }
-        }
-
-        Some(count)
-    }
 }
 
 pub struct Day25Solver {}
 
 impl Solver for Day25Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day25/input.txt").unwrap();
-        let components = Components::new(file.lines());
-        let (partition1_len, partition2_len) = components.find_two_partition_lengths_in_3_cuts();
-        println!(
-            "The sizes of the two partitions are {} x {} = {}",
-            partition1_len,
-            partition2_len,
-            partition1_len * partition2_len
-        );
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 25;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let components = Components::new(input.lines())?;
+        let Some((partition1_len, partition2_len)) =
+            components.find_two_partition_lengths_in_3_cuts()
+        else {
+            // There is no offending text to point at, so the error is at the end of the input.
+            return Err(ParseError::new(
+                &input[input.len()..],
+                "components split in two groups by cutting 3 wires",
+            ));
+        };
+        Ok((partition1_len * partition2_len).into())
     }
 
-    fn solve_part2() {
+    fn solve_part2(_input: &str) -> ParseResult<Answer> {
         // There is no problem 2.
+        Ok(Answer::Empty)
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Components::new(input.lines())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day25Solver::solve_part1(EXAMPLE).unwrap().to_string(), "54");
     }
 }
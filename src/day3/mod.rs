@@ -1,10 +1,11 @@
-use crate::solver::Solver;
+use crate::geometry::Position;
+use crate::grid::Grid;
+use crate::parse::{self, ParseResult};
+use crate::solver::{Answer, Solver};
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::rc::Rc;
 
-const GRID_SIZE: usize = 140;
-
 #[derive(Debug)]
 struct PartNumberCandidate {
     number: u32,
@@ -22,14 +23,8 @@ impl PartNumberCandidate {
     }
 }
 
-#[derive(Debug)]
-struct Position {
-    row: usize,
-    col: usize,
-}
-
 type PartNumberCandidateRow = Vec<Option<Rc<RefCell<PartNumberCandidate>>>>;
-type PartNumberCandidateMatrix = Vec<PartNumberCandidateRow>;
+type PartNumberCandidateMatrix = Grid<Option<Rc<RefCell<PartNumberCandidate>>>>;
 type PartNumberCandidatePositions = Vec<Vec<Position>>;
 
 #[derive(Default)]
@@ -44,9 +39,9 @@ impl Day3Solver {
         col: usize,
         line: &str,
         part_number_candidate_row: &mut PartNumberCandidateRow,
-    ) {
+    ) -> ParseResult<()> {
         if let Some(start_col) = part_number_candidate_start {
-            let candidate_num: u32 = line[*start_col..col].parse().unwrap();
+            let candidate_num: u32 = parse::number(&line[*start_col..col])?;
             let part_number_candidate = Rc::new(RefCell::new(PartNumberCandidate::new(
                 candidate_num,
                 self.part_number_candidate_id,
@@ -59,75 +54,18 @@ impl Day3Solver {
 
             *part_number_candidate_start = None;
         }
-    }
-
-    fn add_adjacent_positions(
-        all_adjacent_positions: &mut PartNumberCandidatePositions,
-        position: Position,
-    ) {
-        let Position { row, col } = position;
-        let first_row = row == 0;
-        let first_col = col == 0;
-        let last_row = row == (GRID_SIZE - 1);
-        let last_col = col == (GRID_SIZE - 1);
-
-        let mut adjacent_positions = Vec::new();
-        if !first_row {
-            if !first_col {
-                adjacent_positions.push(Position {
-                    row: row - 1,
-                    col: col - 1,
-                });
-            }
-
-            adjacent_positions.push(Position { row: row - 1, col });
-
-            if !last_col {
-                adjacent_positions.push(Position {
-                    row: row - 1,
-                    col: col + 1,
-                });
-            }
-        }
-
-        if !first_col {
-            adjacent_positions.push(Position { row, col: col - 1 });
-        }
-
-        if !last_col {
-            adjacent_positions.push(Position { row, col: col + 1 });
-        }
-
-        if !last_row {
-            if !first_col {
-                adjacent_positions.push(Position {
-                    row: row + 1,
-                    col: col - 1,
-                });
-            }
 
-            adjacent_positions.push(Position { row: row + 1, col });
-
-            if !last_col {
-                adjacent_positions.push(Position {
-                    row: row + 1,
-                    col: col + 1,
-                });
-            }
-        }
-
-        all_adjacent_positions.push(adjacent_positions);
+        Ok(())
     }
 
     fn build_part_number_candidates_map_and_positions(
         &mut self,
-        file: String,
+        input: &str,
         symbol_filter: Option<char>,
-    ) -> (PartNumberCandidateMatrix, PartNumberCandidatePositions) {
-        let mut part_number_candidate_matrix = PartNumberCandidateMatrix::new();
-        let mut part_number_candidate_positions = PartNumberCandidatePositions::new();
+    ) -> ParseResult<(PartNumberCandidateMatrix, PartNumberCandidatePositions)> {
+        let mut symbol_positions = Vec::new();
 
-        for (row, line) in file.lines().enumerate() {
+        let part_number_candidate_matrix = Grid::parse_rows(input.lines(), |row, line| {
             let mut part_number_candidate_row = Vec::new();
             let mut part_number_candidate_start = None;
 
@@ -146,7 +84,7 @@ impl Day3Solver {
                         col,
                         line,
                         &mut part_number_candidate_row,
-                    );
+                    )?;
 
                     // The current character is definitely not a part number,
                     // so we can just insert None.
@@ -159,10 +97,7 @@ impl Day3Solver {
                     };
 
                     if add_adjacent_positions {
-                        Self::add_adjacent_positions(
-                            &mut part_number_candidate_positions,
-                            Position { row, col },
-                        );
+                        symbol_positions.push(Position::new(row, col));
                     }
                 }
             }
@@ -173,28 +108,34 @@ impl Day3Solver {
                 line.len(),
                 line,
                 &mut part_number_candidate_row,
-            );
-            part_number_candidate_matrix.push(part_number_candidate_row);
-        }
+            )?;
+            Ok(part_number_candidate_row)
+        })?;
+
+        let part_number_candidate_positions = symbol_positions
+            .into_iter()
+            .map(|position| part_number_candidate_matrix.neighbours8(position).collect())
+            .collect();
 
-        (
+        Ok((
             part_number_candidate_matrix,
             part_number_candidate_positions,
-        )
+        ))
     }
 }
 
 impl Solver for Day3Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day3/input.txt").unwrap();
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 3;
 
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
         let mut solver = Day3Solver::default();
         let (part_number_candidate_matrix, part_number_candidate_positions) =
-            solver.build_part_number_candidates_map_and_positions(file, None);
+            solver.build_part_number_candidates_map_and_positions(input, None)?;
 
         let mut sum_part_numbers = 0;
-        for Position { row, col } in part_number_candidate_positions.into_iter().flatten() {
-            if let Some(part_number_candidate) = &part_number_candidate_matrix[row][col] {
+        for position in part_number_candidate_positions.into_iter().flatten() {
+            if let Some(part_number_candidate) = &part_number_candidate_matrix[position] {
                 let mut part_number_candidate = part_number_candidate.borrow_mut();
                 if part_number_candidate.valid {
                     continue;
@@ -205,23 +146,21 @@ impl Solver for Day3Solver {
             }
         }
 
-        println!("The sum of all part numbers is {sum_part_numbers}.");
+        Ok(sum_part_numbers.into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day3/input.txt").unwrap();
-
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
         let mut solver = Day3Solver::default();
         let (part_number_candidate_matrix, part_number_candidate_positions) =
-            solver.build_part_number_candidates_map_and_positions(file, Some('*'));
+            solver.build_part_number_candidates_map_and_positions(input, Some('*'))?;
 
         let mut sum_gear_ratios = 0;
         for gear_adjacent_positions in part_number_candidate_positions.into_iter() {
             let mut num_adjacent_candidates = 0;
             let mut gear_ratio = 1;
             let mut unique_gear_parts = HashSet::new();
-            for Position { row, col } in gear_adjacent_positions {
-                if let Some(gear_part_number) = &part_number_candidate_matrix[row][col] {
+            for position in gear_adjacent_positions {
+                if let Some(gear_part_number) = &part_number_candidate_matrix[position] {
                     let gear_part_number = gear_part_number.borrow();
 
                     if !unique_gear_parts.insert(gear_part_number.id) {
@@ -237,6 +176,34 @@ impl Solver for Day3Solver {
             }
         }
 
-        println!("The sum of all part numbers is {sum_gear_ratios}.");
+        Ok(sum_gear_ratios.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Day3Solver::default().build_part_number_candidates_map_and_positions(input, None)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day3Solver::solve_part1(EXAMPLE).unwrap().to_string(),
+            "4361"
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day3Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "467835"
+        );
     }
 }
@@ -1,80 +1,82 @@
 use std::collections::HashSet;
 
-use crate::solver::Solver;
+use crate::parse::{self, ParseResult};
+use crate::solver::{Answer, Solver};
 
 pub struct Day4Solver {}
+
+impl Day4Solver {
+    // Counts the numbers of the card that are winning numbers.
+    fn count_matching_numbers(line: &str) -> ParseResult<usize> {
+        let (_, all_numbers) = parse::split_once(line, ":")?;
+        let (winning_numbers, your_numbers) = parse::split_once(all_numbers, "|")?;
+        let winning_numbers: HashSet<u32> = parse::numbers(winning_numbers)?.into_iter().collect();
+        let your_numbers: HashSet<u32> = parse::numbers(your_numbers)?.into_iter().collect();
+
+        Ok(winning_numbers.intersection(&your_numbers).count())
+    }
+}
+
 impl Solver for Day4Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day4/input.txt").unwrap();
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 4;
 
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
         let mut total_points = 0;
-        for line in file.lines() {
-            let (_, all_numbers) = line.split_once(":").unwrap();
-            let (winning_numbers, your_numbers) = all_numbers.split_once("|").unwrap();
-            let winning_numbers: HashSet<_> = winning_numbers
-                .trim()
-                .split(" ")
-                // Splitting can result in empty strings as entries. Filter them out.
-                .filter(|number| !number.is_empty())
-                .collect();
-            let your_numbers: HashSet<_> = your_numbers
-                .trim()
-                .split(" ")
-                // Splitting can result in empty strings as entries. Filter them out.
-                .filter(|number| !number.is_empty())
-                .collect();
-            let num_match = winning_numbers
-                .intersection(&your_numbers)
-                .collect::<Vec<_>>()
-                .len() as u32;
+        for line in input.lines() {
+            let num_match = Self::count_matching_numbers(line)? as u32;
 
             if num_match > 0 {
                 total_points += 2u32.pow(num_match - 1);
             }
         }
 
-        println!("The total number of points is {total_points}.");
+        Ok(total_points.into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day4/input.txt").unwrap();
-
-        let mut wins_per_card = Vec::new();
-        for line in file.lines() {
-            let (_, all_numbers) = line.split_once(":").unwrap();
-            let (winning_numbers, your_numbers) = all_numbers.split_once("|").unwrap();
-            let winning_numbers: HashSet<_> = winning_numbers
-                .trim()
-                .split(" ")
-                // Splitting can result in empty strings as entries. Filter them out.
-                .filter(|number| !number.is_empty())
-                .collect();
-            let your_numbers: HashSet<_> = your_numbers
-                .trim()
-                .split(" ")
-                // Splitting can result in empty strings as entries. Filter them out.
-                .filter(|number| !number.is_empty())
-                .collect();
-            let num_match = winning_numbers
-                .intersection(&your_numbers)
-                .collect::<Vec<_>>()
-                .len();
-
-            wins_per_card.push(num_match);
-        }
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let wins_per_card = input
+            .lines()
+            .map(Self::count_matching_numbers)
+            .collect::<ParseResult<Vec<_>>>()?;
 
         let mut scratchcards_copies = vec![1; wins_per_card.len()];
         for (i, win_per_card) in wins_per_card.into_iter().enumerate() {
             let scratchard_copies = scratchcards_copies[i];
 
-            for j in (i + 1)..=(i + win_per_card) {
-                scratchcards_copies[j] += scratchard_copies;
+            for copies in scratchcards_copies
+                .iter_mut()
+                .skip(i + 1)
+                .take(win_per_card)
+            {
+                *copies += scratchard_copies;
             }
         }
 
-        println!(
-            "The total number of scratchcards is {:?}",
-            scratchcards_copies.into_iter().sum::<usize>()
-        );
+        Ok(scratchcards_copies.into_iter().sum::<usize>().into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        for line in input.lines() {
+            Self::count_matching_numbers(line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day4Solver::solve_part1(EXAMPLE).unwrap().to_string(), "13");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day4Solver::solve_part2(EXAMPLE).unwrap().to_string(), "30");
     }
 }
@@ -1,10 +1,12 @@
-use crate::solver::Solver;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 use std::ops::Range;
 use std::str::Lines;
 use std::sync::mpsc::channel;
 
+/// Converts a range of source values to their destination values.
 #[derive(Clone, Debug)]
-struct ConversionMap {
+pub struct ConversionMap {
     // Start bound is inclusive.
     start_bound: i64,
     // End bound is exclusive.
@@ -16,7 +18,7 @@ struct ConversionMap {
 }
 
 impl ConversionMap {
-    fn convert_if_inbound(&self, key: i64) -> Option<i64> {
+    pub fn convert_if_inbound(&self, key: i64) -> Option<i64> {
         if key >= self.start_bound && key < self.end_bound {
             Some(key + self.conversion)
         } else {
@@ -28,57 +30,63 @@ impl ConversionMap {
 pub struct Day5Solver {}
 
 impl Day5Solver {
-    fn parse_seeds_list_part1(lines: &mut Lines) -> Vec<i64> {
-        let seeds_line = lines.next().unwrap();
-        let (_, seeds) = seeds_line.split_once(": ").unwrap();
-        let parsed_seeds = seeds.split(' ').flat_map(str::parse::<i64>).collect();
+    /// Parses the seeds line as a list of individual seeds.
+    pub fn parse_seeds_list_part1(lines: &mut Lines) -> ParseResult<Vec<i64>> {
+        let seeds_line = parse::next(lines, "", "the seeds")?;
+        let seeds = parse::strip_prefix(seeds_line, "seeds: ")?;
+        let parsed_seeds: Vec<i64> = parse::numbers(seeds)?;
+        if parsed_seeds.is_empty() {
+            return Err(ParseError::new(seeds, "a seed"));
+        }
 
         // Skip the following empty line.
         lines.next();
 
-        parsed_seeds
+        Ok(parsed_seeds)
     }
 
-    fn parse_seeds_list_part2(lines: &mut Lines) -> Vec<Range<i64>> {
-        let seeds_line = lines.next().unwrap();
-        let (_, seeds) = seeds_line.split_once(": ").unwrap();
-        let parsed_seeds_and_ranges: Vec<_> =
-            seeds.split(' ').flat_map(str::parse::<i64>).collect();
+    /// Parses the seeds line as a list of seed ranges.
+    pub fn parse_seeds_list_part2(lines: &mut Lines) -> ParseResult<Vec<Range<i64>>> {
+        let seeds_line = parse::next(lines, "", "the seeds")?;
+        let seeds = parse::strip_prefix(seeds_line, "seeds: ")?;
+        let seeds_and_ranges: Vec<_> = seeds.split_whitespace().collect();
+        if seeds_and_ranges.is_empty() || !seeds_and_ranges.len().is_multiple_of(2) {
+            return Err(ParseError::new(seeds, "pairs of seeds and ranges"));
+        }
 
         // Process seed ranges in pairs. Even entries are a seed, and the subsequent
         // odd entry is the range the preceding seed applies to.
-        let mut i = 0;
-        let parsed_seeds_and_ranges_len = parsed_seeds_and_ranges.len();
         let mut parsed_seed_ranges = Vec::new();
-        while (i + 1) < parsed_seeds_and_ranges_len {
-            let parsed_seed_start = parsed_seeds_and_ranges[i];
-            let range = parsed_seeds_and_ranges[i + 1];
-            let parsed_seed_end = parsed_seed_start + range;
-            parsed_seed_ranges.push(parsed_seed_start..parsed_seed_end);
-
-            i += 2;
+        for seed_and_range in seeds_and_ranges.chunks(2) {
+            let parsed_seed_start: i64 = parse::number(seed_and_range[0])?;
+            let range: i64 = parse::number(seed_and_range[1])?;
+            if range <= 0 {
+                return Err(ParseError::new(seed_and_range[1], "a positive range"));
+            }
+            parsed_seed_ranges.push(parsed_seed_start..parsed_seed_start + range);
         }
 
         // Skip the following empty line.
         lines.next();
 
-        parsed_seed_ranges
+        Ok(parsed_seed_ranges)
     }
 
-    fn parse_category_conversion_maps(lines: &mut Lines) -> Vec<ConversionMap> {
+    /// Parses one category's conversion maps, sorted by their start bound.
+    pub fn parse_category_conversion_maps(lines: &mut Lines) -> ParseResult<Vec<ConversionMap>> {
         let mut category_conversion_maps = Vec::new();
 
-        while let Some(line) = lines.next() {
+        for line in lines.by_ref() {
             if line.is_empty() {
                 break;
             }
 
-            let (destination_start, source_start_and_range) = line.split_once(' ').unwrap();
-            let (source_start, range) = source_start_and_range.split_once(' ').unwrap();
+            let (destination_start, source_start_and_range) = parse::split_once(line, " ")?;
+            let (source_start, range) = parse::split_once(source_start_and_range, " ")?;
 
-            let destination_start: i64 = destination_start.parse().unwrap();
-            let source_start: i64 = source_start.parse().unwrap();
-            let range: i64 = range.parse().unwrap();
+            let destination_start: i64 = parse::number(destination_start)?;
+            let source_start: i64 = parse::number(source_start)?;
+            let range: i64 = parse::number(range)?;
 
             let start_bound = source_start;
             let end_bound = start_bound + range;
@@ -91,11 +99,29 @@ impl Day5Solver {
             });
         }
 
-        category_conversion_maps.sort_by(|a, b| a.start_bound.cmp(&b.start_bound));
-        category_conversion_maps
+        category_conversion_maps.sort_by_key(|map| map.start_bound);
+        Ok(category_conversion_maps)
     }
 
-    fn find_seed_location_number(
+    /// Parses the conversion maps of every category, each following its header line. A category
+    /// must have a conversion map.
+    pub fn parse_categories_conversion_maps(
+        lines: &mut Lines,
+    ) -> ParseResult<Vec<Vec<ConversionMap>>> {
+        let mut categories_conversion_maps = Vec::new();
+        while let Some(header) = lines.next() {
+            let category_conversion_maps = Self::parse_category_conversion_maps(lines)?;
+            if category_conversion_maps.is_empty() {
+                return Err(ParseError::new(header, "a category with a conversion map"));
+            }
+            categories_conversion_maps.push(category_conversion_maps);
+        }
+
+        Ok(categories_conversion_maps)
+    }
+
+    /// Converts a seed through all categories, down to its location number.
+    pub fn find_seed_location_number(
         seed: i64,
         categories_conversion_maps: &Vec<Vec<ConversionMap>>,
     ) -> i64 {
@@ -134,36 +160,28 @@ impl Day5Solver {
 }
 
 impl Solver for Day5Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day5/input.txt").unwrap();
-        let mut lines = file.lines();
-        let parsed_seeds = Self::parse_seeds_list_part1(&mut lines);
-
-        let mut categories_conversion_maps = Vec::new();
-        while lines.next().is_some() {
-            categories_conversion_maps.push(Self::parse_category_conversion_maps(&mut lines));
-        }
-
-        println!(
-            "The lowest location number is {}",
-            parsed_seeds
-                .iter()
-                .map(|seed| Self::find_seed_location_number(*seed, &categories_conversion_maps))
-                .min()
-                .unwrap()
-        );
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 5;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let mut lines = input.lines();
+        let parsed_seeds = Self::parse_seeds_list_part1(&mut lines)?;
+        let categories_conversion_maps = Self::parse_categories_conversion_maps(&mut lines)?;
+
+        // There is at least a seed.
+        Ok(parsed_seeds
+            .iter()
+            .map(|seed| Self::find_seed_location_number(*seed, &categories_conversion_maps))
+            .min()
+            .unwrap()
+            .into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day5/input.txt").unwrap();
-        let mut lines = file.lines();
-        let parsed_seed_ranges = Self::parse_seeds_list_part2(&mut lines);
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let mut lines = input.lines();
+        let parsed_seed_ranges = Self::parse_seeds_list_part2(&mut lines)?;
         let num_parsed_seed_ranges = parsed_seed_ranges.len();
-
-        let mut categories_conversion_maps = Vec::new();
-        while lines.next().is_some() {
-            categories_conversion_maps.push(Self::parse_category_conversion_maps(&mut lines));
-        }
+        let categories_conversion_maps = Self::parse_categories_conversion_maps(&mut lines)?;
 
         let (tx, rx) = channel();
         for parsed_seed_range in parsed_seed_ranges {
@@ -185,9 +203,31 @@ impl Solver for Day5Solver {
             global_lowest_location_number = global_lowest_location_number.min(rx.recv().unwrap());
         }
 
-        println!(
-            "The lowest location number is {}",
-            global_lowest_location_number
-        );
+        Ok(global_lowest_location_number.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        // Part 2 reads the seeds as pairs, so it rejects every list part 1's would.
+        let mut lines = input.lines();
+        Self::parse_seeds_list_part2(&mut lines)?;
+        Self::parse_categories_conversion_maps(&mut lines)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day5Solver::solve_part1(EXAMPLE).unwrap().to_string(), "35");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day5Solver::solve_part2(EXAMPLE).unwrap().to_string(), "46");
     }
 }
@@ -1,53 +1,116 @@
-use crate::solver::Solver;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 pub struct Day6Solver {}
 
 impl Day6Solver {
-    fn find_number_of_ways_to_win((time, distance): (f64, f64)) -> i64 {
-        let sqrt = f64::sqrt(time.powi(2) - 4.0 * distance);
-        let first_solution = ((time - sqrt) / 2.0).ceil() as i64;
-        let last_solution = ((time + sqrt) / 2.0).floor() as i64;
+    /// Counts the button hold times that beat the record `distance` within `time`.
+    pub fn find_number_of_ways_to_win((time, distance): (f64, f64)) -> i64 {
+        let discriminant = time.powi(2) - 4.0 * distance;
+        // Without two distinct roots, holding the button never beats the record.
+        if discriminant <= 0.0 {
+            return 0;
+        }
+
+        let sqrt = discriminant.sqrt();
+        // Holding the button exactly as long as a root only ties the record, thus the +1 and -1.
+        let first_solution = ((time - sqrt) / 2.0).floor() as i64 + 1;
+        let last_solution = ((time + sqrt) / 2.0).ceil() as i64 - 1;
 
-        // The count should include the first and last solutions, thus the +1.
         last_solution - first_solution + 1
     }
+
+    // Returns the times and distances, which follow their labels on the first two lines.
+    fn parse_times_and_distances(input: &str) -> ParseResult<(&str, &str)> {
+        let mut lines = input.lines();
+        let time_line = parse::next(&mut lines, input, "the times")?;
+        let distance_line = parse::next(&mut lines, time_line, "the distances")?;
+
+        let times = parse::strip_prefix(time_line, "Time:")?;
+        let distances = parse::strip_prefix(distance_line, "Distance:")?;
+        Ok((times, distances))
+    }
+
+    // Parses the number whose digits are spread across the text, separated by whitespace.
+    fn parse_merged_number(text: &str) -> ParseResult<f64> {
+        parse::numbers::<u64>(text)?;
+        let merged: String = text.split_whitespace().collect();
+        merged
+            .parse()
+            .map_err(|_| ParseError::new(text, "a number"))
+    }
+
+    // Returns the time and record distance of each race.
+    fn parse_races(input: &str) -> ParseResult<Vec<(f64, f64)>> {
+        let (times, distances) = Self::parse_times_and_distances(input)?;
+        let times_list: Vec<f64> = parse::numbers(times)?;
+        let distances_list: Vec<f64> = parse::numbers(distances)?;
+        if times_list.len() != distances_list.len() {
+            return Err(ParseError::new(distances, "as many distances as times"));
+        }
+
+        Ok(std::iter::zip(times_list, distances_list).collect())
+    }
+
+    // Returns the time and record distance of the single race, once the kerning is fixed.
+    fn parse_merged_race(input: &str) -> ParseResult<(f64, f64)> {
+        let (times, distances) = Self::parse_times_and_distances(input)?;
+
+        let merged_times: f64 = Self::parse_merged_number(times)?;
+        let merged_distances: f64 = Self::parse_merged_number(distances)?;
+        Ok((merged_times, merged_distances))
+    }
 }
 
 impl Solver for Day6Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day6/input.txt").unwrap();
-        let mut lines = file.lines();
-        let time_line = lines.next().unwrap();
-        let distance_line = lines.next().unwrap();
-
-        let (_, times) = time_line.split_once(':').unwrap();
-        let (_, distances) = distance_line.split_once(':').unwrap();
-        let times = times.trim();
-        let distances = distances.trim();
-
-        let product: i64 = std::iter::zip(times.split_whitespace(), distances.split_whitespace())
-            .map(|(time, distance)| (time.parse().unwrap(), distance.parse().unwrap()))
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 6;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let product: i64 = Self::parse_races(input)?
+            .into_iter()
             .map(Self::find_number_of_ways_to_win)
             .product();
 
-        println!("The product of all ways to win this game is {product}");
+        Ok(product.into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let num_of_ways_to_win = Self::find_number_of_ways_to_win(Self::parse_merged_race(input)?);
+        Ok(num_of_ways_to_win.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Self::parse_races(input)?;
+        Self::parse_merged_race(input)?;
+        Ok(())
     }
+}
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day6/input.txt").unwrap();
-        let mut lines = file.lines();
-        let time_line = lines.next().unwrap();
-        let distance_line = lines.next().unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let (_, times) = time_line.split_once(':').unwrap();
-        let (_, distances) = distance_line.split_once(':').unwrap();
+    const EXAMPLE: &str = include_str!("input-small.txt");
 
-        let merged_times: String = times.split_whitespace().collect();
-        let merged_distances: String = distances.split_whitespace().collect();
-        let merged_times: f64 = merged_times.parse().unwrap();
-        let merged_distances: f64 = merged_distances.parse().unwrap();
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day6Solver::solve_part1(EXAMPLE).unwrap().to_string(), "288");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day6Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "71503"
+        );
+    }
 
-        let num_of_ways_to_win = Self::find_number_of_ways_to_win((merged_times, merged_distances));
-        println!("The number of ways to win this merged race is {num_of_ways_to_win}");
+    #[test]
+    fn unbeatable_record() {
+        assert_eq!(Day6Solver::find_number_of_ways_to_win((7.0, 100.0)), 0);
+        // Holding the button for 2 ms ties the record of the tangent root, without beating it.
+        assert_eq!(Day6Solver::find_number_of_ways_to_win((4.0, 4.0)), 0);
+        assert_eq!(Day6Solver::find_number_of_ways_to_win((5.0, 6.0)), 0);
     }
 }
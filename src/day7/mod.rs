@@ -1,13 +1,14 @@
 use std::cmp::Ordering;
 
-use crate::solver::Solver;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
-const HAND_LENGTH: usize = 5;
+pub const HAND_LENGTH: usize = 5;
 
 // Note: the default ordering assumes the enum variants are in
 // ascending order.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
-enum Card {
+pub enum Card {
     #[default]
     Ace,
     King,
@@ -26,8 +27,8 @@ enum Card {
 }
 
 impl Card {
-    fn into(value: char, jack_as_joker: bool) -> Self {
-        match value {
+    pub fn from_char(value: char, jack_as_joker: bool) -> Option<Self> {
+        let card = match value {
             'A' => Card::Ace,
             'K' => Card::King,
             'Q' => Card::Queen,
@@ -42,15 +43,17 @@ impl Card {
             '4' => Card::Four,
             '3' => Card::Three,
             '2' => Card::Two,
-            _ => unreachable!(),
-        }
+            _ => return None,
+        };
+
+        Some(card)
     }
 }
 
 // Note: the default ordering assumes the enum variants are in
 // ascending order.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum HandType {
+pub enum HandType {
     FiveOfAKind,
     FourOfAKind,
     FullHouse,
@@ -61,10 +64,10 @@ enum HandType {
 }
 
 #[derive(Debug, Eq)]
-struct Hand {
-    cards: [Card; HAND_LENGTH],
-    hand_type: HandType,
-    bid: u32,
+pub struct Hand {
+    pub cards: [Card; HAND_LENGTH],
+    pub hand_type: HandType,
+    pub bid: u32,
 }
 
 impl PartialEq for Hand {
@@ -90,7 +93,7 @@ impl Ord for Hand {
 }
 
 impl Hand {
-    fn find_hand_type(mut cards: [Card; HAND_LENGTH]) -> HandType {
+    pub fn find_hand_type(mut cards: [Card; HAND_LENGTH]) -> HandType {
         cards.sort();
 
         let mut has_quint = false;
@@ -101,9 +104,7 @@ impl Hand {
         let mut prev_card = cards[0];
         let mut num_prev_same = 1;
         let mut num_jokers = 0;
-        for i in 1..HAND_LENGTH {
-            let curr_card = cards[i];
-
+        for (i, curr_card) in cards.into_iter().enumerate().skip(1) {
             if curr_card == prev_card {
                 num_prev_same += 1;
             }
@@ -168,52 +169,87 @@ impl Hand {
         }
     }
 
-    fn parse_new(line: &str, jack_as_joker: bool) -> Self {
-        let (cards_str, bid) = line.split_once(' ').unwrap();
-        let bid: u32 = bid.parse().unwrap();
-        let mut cards = [Card::default(); HAND_LENGTH];
-        for (i, card) in cards_str.chars().enumerate() {
-            cards[i] = Card::into(card, jack_as_joker);
-        }
+    /// Parses a line made of a hand of cards followed by its bid.
+    pub fn parse_new(line: &str, jack_as_joker: bool) -> ParseResult<Self> {
+        let (cards_str, bid) = parse::split_once(line, " ")?;
+        let bid: u32 = parse::number(bid)?;
+        let cards: Vec<_> = parse::chars(cards_str, "a card", |card| {
+            Card::from_char(card, jack_as_joker)
+        })
+        .collect::<ParseResult<_>>()?;
+        let cards: [Card; HAND_LENGTH] = cards
+            .try_into()
+            .map_err(|_| ParseError::new(cards_str, format!("{HAND_LENGTH} cards")))?;
 
         let hand_type = Self::find_hand_type(cards);
 
-        Self {
+        Ok(Self {
             cards,
             hand_type,
             bid,
-        }
+        })
     }
 }
 
 pub struct Day7Solver {}
 
 impl Day7Solver {
-    fn solve_camel_cards_game(jack_as_joker: bool) {
-        let file = std::fs::read_to_string("src/day7/input.txt").unwrap();
-        let mut hands: Vec<_> = file
+    /// Returns the total winnings of all hands, ranked from worst to best.
+    pub fn solve_camel_cards_game(input: &str, jack_as_joker: bool) -> ParseResult<u32> {
+        let mut hands = input
             .lines()
             .map(|line| Hand::parse_new(line, jack_as_joker))
-            .collect();
+            .collect::<ParseResult<Vec<_>>>()?;
         // Sort in descending order, as rank 1 is the worst hand.
         hands.sort_by(|a, b| b.cmp(a));
 
-        let total_winnings: u32 = hands
+        Ok(hands
             .iter()
             .enumerate()
             .map(|(i, hand)| (i + 1) as u32 * hand.bid)
-            .sum();
-
-        println!("The sum of all total winnings is {total_winnings}");
+            .sum())
     }
 }
 
 impl Solver for Day7Solver {
-    fn solve_part1() {
-        Self::solve_camel_cards_game(false);
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 7;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_camel_cards_game(input, false)?.into())
+    }
+
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        Ok(Self::solve_camel_cards_game(input, true)?.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        for line in input.lines() {
+            Hand::parse_new(line, false)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day7Solver::solve_part1(EXAMPLE).unwrap().to_string(),
+            "6440"
+        );
     }
 
-    fn solve_part2() {
-        Self::solve_camel_cards_game(true);
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day7Solver::solve_part2(EXAMPLE).unwrap().to_string(),
+            "5905"
+        );
     }
 }
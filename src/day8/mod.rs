@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 
-use crate::solver::Solver;
+use crate::math::Congruence;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::solver::{Answer, Solver};
 
 const START_NODE: &str = "AAA";
 const END_NODE: &str = "ZZZ";
@@ -11,84 +13,225 @@ enum Step {
     Right,
 }
 
-impl From<char> for Step {
-    fn from(value: char) -> Self {
+impl Step {
+    fn from_char(value: char) -> Option<Self> {
         match value {
-            'L' => Step::Left,
-            'R' => Step::Right,
-            _ => unreachable!(),
+            'L' => Some(Step::Left),
+            'R' => Some(Step::Right),
+            _ => None,
         }
     }
 }
 
-pub struct Day8Solver {}
-
-impl Day8Solver {
-    fn gcd(a: u64, b: u64) -> u64 {
-        if b == 0 {
-            a
-        } else {
-            Self::gcd(b, a % b)
-        }
-    }
-
-    fn lcm(a: u64, b: u64) -> u64 {
-        (a / Self::gcd(a, b)) * b
-    }
+/// The steps to follow, and the nodes of the network with the nodes on their left and right.
+struct Network<'a> {
+    steps: Vec<Step>,
+    nodes: HashMap<&'a str, (&'a str, &'a str)>,
 }
 
-impl Solver for Day8Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day8/input.txt").unwrap();
-        let mut lines = file.lines();
-        let steps: Vec<_> = lines.next().unwrap().chars().map(Step::from).collect();
-        let steps_len = steps.len();
+impl<'a> Network<'a> {
+    fn new(input: &'a str) -> ParseResult<Self> {
+        let mut lines = input.lines();
+        let steps_line = parse::next(&mut lines, input, "the steps")?;
+        let steps: Vec<_> =
+            parse::chars(steps_line, "a step", Step::from_char).collect::<ParseResult<_>>()?;
+        if steps.is_empty() {
+            return Err(ParseError::new(steps_line, "at least one step"));
+        }
 
         // Skip empty line.
         lines.next();
 
         let mut nodes = HashMap::new();
         for line in lines {
-            let (from_node, to_nodes) = line.split_once('=').unwrap();
+            let (from_node, to_nodes) = parse::split_once(line, "=")?;
             let from_node = from_node.trim();
-            let (left_to_node, right_to_node) = to_nodes.split_once(',').unwrap();
-            let left_to_node = left_to_node.trim().strip_prefix('(').unwrap();
-            let right_to_node = right_to_node.trim().strip_suffix(')').unwrap();
+            let (left_to_node, right_to_node) = parse::split_once(to_nodes, ",")?;
+            let left_to_node = parse::strip_prefix(left_to_node.trim(), "(")?;
+            let right_to_node = right_to_node.trim();
+            let right_to_node = right_to_node
+                .strip_suffix(')')
+                .ok_or_else(|| ParseError::new(right_to_node, "`)`"))?;
 
             nodes.insert(from_node, (left_to_node, right_to_node));
         }
 
+        Ok(Self { steps, nodes })
+    }
+
+    // Returns the node reached from the node by following the `i`-th step.
+    fn next(&self, node: &'a str, i: usize) -> ParseResult<&'a str> {
+        let (left_node, right_node) = self
+            .nodes
+            .get(node)
+            .ok_or_else(|| ParseError::new(node, "a node with a line of its own"))?;
+
+        Ok(match self.steps[i % self.steps.len()] {
+            Step::Left => left_node,
+            Step::Right => right_node,
+        })
+    }
+
+    // Follows a ghost traversal from the node until it loops, i.e. reaches the same node at the
+    // same step of the instructions.
+    fn schedule(&self, start_node: &'a str) -> ParseResult<Schedule> {
+        let mut iterations = HashMap::new();
+        let mut end_iterations = Vec::new();
+        let mut curr_node = start_node;
+        let mut i = 0;
+        loop {
+            let state = (curr_node, i % self.steps.len());
+            if let Some(cycle_start) = iterations.insert(state, i) {
+                let period = (i - cycle_start) as i64;
+                let (before_cycle, in_cycle): (Vec<_>, Vec<_>) = end_iterations
+                    .into_iter()
+                    .partition(|end_iteration| *end_iteration < cycle_start);
+
+                return Ok(Schedule {
+                    before_cycle,
+                    cycle_start,
+                    cycle: in_cycle
+                        .into_iter()
+                        .map(|end_iteration| Congruence::new(end_iteration as i64, period))
+                        .collect(),
+                });
+            }
+
+            if curr_node.ends_with('Z') {
+                end_iterations.push(i);
+            }
+
+            curr_node = self.next(curr_node, i)?;
+            i += 1;
+        }
+    }
+}
+
+/// The iterations at which a ghost traversal is on a node ending in Z.
+struct Schedule {
+    // The iterations before the traversal loops.
+    before_cycle: Vec<usize>,
+    // The iteration at which the loop starts, which the traversal then repeats forever.
+    cycle_start: usize,
+    cycle: Vec<Congruence>,
+}
+
+impl Schedule {
+    fn is_end(&self, i: usize) -> bool {
+        self.before_cycle.contains(&i)
+            || (i >= self.cycle_start
+                && self
+                    .cycle
+                    .iter()
+                    .any(|congruence| congruence.contains(i as i64)))
+    }
+}
+
+pub struct Day8Solver {}
+
+impl Solver for Day8Solver {
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 8;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let network = Network::new(input)?;
+
         let mut curr_node = START_NODE;
         let mut i = 0;
         while curr_node != END_NODE {
-            let (left_node, right_node) = nodes.get(curr_node).unwrap();
-            curr_node = match steps[i % steps_len] {
-                Step::Left => &left_node,
-                Step::Right => &right_node,
-            };
-
+            curr_node = network.next(curr_node, i)?;
             i += 1;
         }
 
-        println!("It took {i} steps to reach ZZZ");
+        Ok(i.into())
     }
 
-    fn solve_part2() {
-        // These are the periods of all the traversals starting at a node ending in A.
-        // I found these periods by printing the iteration at which each traversal was passing
-        // through a node ending in Z. A pattern emerged that each traversal recurringly
-        // went through a node ending in Z after a fixed period. Therefore, the solution to part
-        // 2 ended being to find the least common multiple (LCM) of all of these periods. The
-        // naïve solution otherwise ended up being way too slow -- the solution being in the
-        // tens of trillions of iterations.
-        let periods: Vec<u64> = vec![19241, 18157, 19783, 16531, 21409, 14363];
-        let lcm = periods
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        // The naïve solution of moving all the ghosts together is way too slow -- the solution
+        // being in the tens of trillions of iterations. Instead, each traversal is followed on
+        // its own until it loops, after which it recurringly goes through the same nodes ending
+        // in Z with a fixed period.
+        let network = Network::new(input)?;
+        let schedules = network
+            .nodes
+            .keys()
+            .filter(|node| node.ends_with('A'))
+            .map(|start_node| network.schedule(start_node))
+            .collect::<ParseResult<Vec<_>>>()?;
+
+        // If the traversals all end at the same time before one of them loops, it is the first
+        // time they do.
+        let before_cycles = schedules
+            .iter()
+            .flat_map(|schedule| &schedule.before_cycle)
+            .copied()
+            .filter(|i| schedules.iter().all(|schedule| schedule.is_end(*i)))
+            .min();
+        if let Some(i) = before_cycles {
+            return Ok(i.into());
+        }
+
+        // Otherwise, they all end at the same time when the periodic iterations of each of them
+        // coincide, for any combination of them. With the real input, each traversal only ends
+        // once per period, at a multiple of the period: this is the least common multiple of the
+        // periods.
+        let mut combinations = vec![Congruence::new(0, 1)];
+        for schedule in &schedules {
+            combinations = combinations
+                .iter()
+                .flat_map(|combination| {
+                    schedule
+                        .cycle
+                        .iter()
+                        .filter_map(|congruence| combination.combine(*congruence))
+                })
+                .collect();
+        }
+
+        let all_cycling = schedules
+            .iter()
+            .map(|schedule| schedule.cycle_start)
+            .max()
+            .unwrap_or_default();
+        combinations
             .into_iter()
-            .reduce(|lcm, period| Self::lcm(lcm, period))
-            .unwrap();
-        println!(
-            "The number of steps at which all ghosts end up in a Z node is {}",
-            lcm
+            .map(|combination| combination.smallest_at_least(all_cycling as i64))
+            .min()
+            .map(|i| i.into())
+            .ok_or_else(|| {
+                // Point at the network's first node, or where it is missing.
+                let first_node = input.lines().nth(2).unwrap_or(&input[input.len()..]);
+                ParseError::new(first_node, "ghosts ending on nodes ending in Z together")
+            })
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Network::new(input)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(
+            Day8Solver::solve_part1(include_str!("input-small1.txt"))
+                .unwrap()
+                .to_string(),
+            "6"
+        );
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(
+            Day8Solver::solve_part2(include_str!("input-small2.txt"))
+                .unwrap()
+                .to_string(),
+            "6"
         );
     }
 }
@@ -1,19 +1,21 @@
-use crate::solver::Solver;
+use crate::parse::{self, ParseResult};
+use crate::solver::{Answer, Solver};
 
 pub struct Day9Solver {}
 
+impl Day9Solver {
+    // Returns the history of values of each line.
+    fn parse_histories(input: &str) -> ParseResult<Vec<Vec<i64>>> {
+        input.lines().map(parse::numbers).collect()
+    }
+}
+
 impl Solver for Day9Solver {
-    fn solve_part1() {
-        let file = std::fs::read_to_string("src/day9/input.txt").unwrap();
-
-        let histories: Vec<Vec<i64>> = file
-            .lines()
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|num| num.parse().unwrap())
-                    .collect()
-            })
-            .collect();
+    const YEAR: u16 = 2023;
+    const DAY: u8 = 9;
+
+    fn solve_part1(input: &str) -> ParseResult<Answer> {
+        let histories = Self::parse_histories(input)?;
 
         // Finding the extrapolated value to the right, if you look closely, is in fact the sum
         // of the last entry in each processed step.
@@ -31,20 +33,11 @@ impl Solver for Day9Solver {
             sum_of_extrapolated_values += extrapolated_value;
         }
 
-        println!("The sum of all extrapolated values is {sum_of_extrapolated_values}");
+        Ok(sum_of_extrapolated_values.into())
     }
 
-    fn solve_part2() {
-        let file = std::fs::read_to_string("src/day9/input.txt").unwrap();
-
-        let histories: Vec<Vec<i64>> = file
-            .lines()
-            .map(|line| {
-                line.split_whitespace()
-                    .map(|num| num.parse().unwrap())
-                    .collect()
-            })
-            .collect();
+    fn solve_part2(input: &str) -> ParseResult<Answer> {
+        let histories = Self::parse_histories(input)?;
 
         let mut sum_of_extrapolated_values = 0;
         for mut history in histories {
@@ -72,6 +65,28 @@ impl Solver for Day9Solver {
             sum_of_extrapolated_values += extrapolated_value;
         }
 
-        println!("The sum of all extrapolated values is {sum_of_extrapolated_values}");
+        Ok(sum_of_extrapolated_values.into())
+    }
+
+    fn parse(input: &str) -> ParseResult<()> {
+        Self::parse_histories(input)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("input-small.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(Day9Solver::solve_part1(EXAMPLE).unwrap().to_string(), "114");
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day9Solver::solve_part2(EXAMPLE).unwrap().to_string(), "2");
     }
 }
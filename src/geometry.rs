@@ -0,0 +1,182 @@
+//! Directions and points on a plane, shared by the puzzles that move around a map.
+//!
+//! Rows and columns are used rather than x and y: the origin (0,0) is the top-left corner, going
+//! down increments the row, and going to the right increments the column.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/// One of the four orthogonal directions. Up is towards the first row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All the directions, clockwise from Up.
+    pub const ALL: [Direction; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+        }
+    }
+
+    /// The direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    /// The direction after a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    /// The offset of a single step in this direction.
+    pub fn offset(self) -> Point {
+        match self {
+            Self::Up => Point::new(-1, 0),
+            Self::Right => Point::new(0, 1),
+            Self::Down => Point::new(1, 0),
+            Self::Left => Point::new(0, -1),
+        }
+    }
+}
+
+/// A point whose row and column can be negative, e.g. on an infinite map, or an offset between
+/// two points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub row: i64,
+    pub col: i64,
+}
+
+impl Point {
+    pub fn new(row: i64, col: i64) -> Self {
+        Self { row, col }
+    }
+
+    /// The number of orthogonal steps to get to the other point.
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.row + other.row, self.col + other.col)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.row - other.row, self.col - other.col)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.row, -self.col)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.row * factor, self.col * factor)
+    }
+}
+
+impl From<Position> for Point {
+    fn from(position: Position) -> Self {
+        Point::new(position.row as i64, position.col as i64)
+    }
+}
+
+/// A point whose row and column can't be negative, e.g. the position of a cell in a grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Returns the position moved by the offset, or `None` if it would be negative.
+    pub fn checked_add(self, offset: Point) -> Option<Position> {
+        Some(Position::new(
+            self.row.checked_add_signed(offset.row.try_into().ok()?)?,
+            self.col.checked_add_signed(offset.col.try_into().ok()?)?,
+        ))
+    }
+
+    /// Returns the position a step away in the direction, or `None` if it would be negative.
+    pub fn step(self, direction: Direction) -> Option<Position> {
+        self.checked_add(direction.offset())
+    }
+}
+
+impl TryFrom<Point> for Position {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok(Position::new(point.row.try_into()?, point.col.try_into()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_and_opposites_are_consistent() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(
+                direction.offset() + direction.opposite().offset(),
+                Point::default()
+            );
+        }
+    }
+
+    #[test]
+    fn positions_cannot_be_negative() {
+        let position = Position::new(0, 2);
+
+        assert_eq!(position.step(Direction::Up), None);
+        assert_eq!(position.step(Direction::Left), Some(Position::new(0, 1)));
+        assert_eq!(
+            position.checked_add(Direction::Down.offset() * 3),
+            Some(Position::new(3, 2))
+        );
+        assert!(Position::try_from(Point::new(1, -1)).is_err());
+        assert_eq!(Point::from(position), Point::new(0, 2));
+    }
+}
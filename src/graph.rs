@@ -0,0 +1,403 @@
+//! Graphs and the search algorithms shared by the puzzles that boil down to one.
+//!
+//! A graph only has to tell the edges leaving each of its nodes, so it can either be built
+//! explicitly as an [`AdjacencyList`], or be implicit, e.g. the states of a search computed on the
+//! fly.
+
+use hashbrown::{HashMap, HashSet};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+
+// How many entries of the open set `a_star` pops between two traces of its size.
+const A_STAR_TRACE_PERIOD: usize = 10_000;
+
+/// A directed graph with weighted edges. An undirected graph has an edge both ways.
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    /// Returns the nodes the node has an edge to, with the weight of the edge.
+    fn edges(&self, node: Self::Node) -> impl Iterator<Item = (Self::Node, u64)>;
+
+    /// Returns the nodes reachable from the start in breadth-first order, with the number of
+    /// edges on the shortest path to them.
+    fn breadth_first_search(&self, start: Self::Node) -> Vec<(Self::Node, usize)> {
+        let mut visited: HashSet<_> = HashSet::from_iter([start]);
+        let mut to_visit = VecDeque::from([(start, 0)]);
+        let mut reached = Vec::new();
+
+        while let Some((node, num_edges)) = to_visit.pop_front() {
+            reached.push((node, num_edges));
+            for (neighbour, _) in self.edges(node) {
+                if visited.insert(neighbour) {
+                    to_visit.push_back((neighbour, num_edges + 1));
+                }
+            }
+        }
+
+        reached
+    }
+
+    /// Returns the weight of the lightest path from any of the starts to a goal, or `None` if no
+    /// goal can be reached. This is Dijkstra's algorithm.
+    fn shortest_path(
+        &self,
+        starts: impl IntoIterator<Item = Self::Node>,
+        is_goal: impl Fn(Self::Node) -> bool,
+    ) -> Option<u64>
+    where
+        Self::Node: Ord,
+    {
+        self.a_star(starts, is_goal, |_| 0)
+    }
+
+    /// Like [`Graph::shortest_path`], but explores first the nodes the heuristic estimates to be
+    /// closer to a goal. This is the A* algorithm: the heuristic must never overestimate the
+    /// weight left to a goal, or the path found may not be the lightest.
+    fn a_star(
+        &self,
+        starts: impl IntoIterator<Item = Self::Node>,
+        is_goal: impl Fn(Self::Node) -> bool,
+        heuristic: impl Fn(Self::Node) -> u64,
+    ) -> Option<u64>
+    where
+        Self::Node: Ord,
+    {
+        // The weight of the lightest path found so far to each node.
+        let mut weights = HashMap::new();
+        // The binary heap is a max-heap, so the entries are reversed to pop the lightest first.
+        let mut open = BinaryHeap::new();
+        for start in starts {
+            weights.insert(start, 0);
+            open.push(Reverse((heuristic(start), 0, start)));
+        }
+
+        let mut pops = 0;
+        while let Some(Reverse((_, weight, node))) = open.pop() {
+            pops += 1;
+            if pops % A_STAR_TRACE_PERIOD == 0 {
+                crate::trace!(
+                    "{pops} entries popped, {} nodes reached, {} entries left in the open set",
+                    weights.len(),
+                    open.len()
+                );
+            }
+
+            // A lighter path to the node was found after this entry was pushed.
+            if weights[&node] < weight {
+                continue;
+            }
+
+            if is_goal(node) {
+                crate::trace!(
+                    "found a path of weight {weight} after reaching {} nodes, {} entries left in the open set",
+                    weights.len(),
+                    open.len()
+                );
+                return Some(weight);
+            }
+
+            for (neighbour, edge_weight) in self.edges(node) {
+                let neighbour_weight = weight + edge_weight;
+                if weights
+                    .get(&neighbour)
+                    .is_some_and(|weight| *weight <= neighbour_weight)
+                {
+                    continue;
+                }
+
+                weights.insert(neighbour, neighbour_weight);
+                open.push(Reverse((
+                    neighbour_weight + heuristic(neighbour),
+                    neighbour_weight,
+                    neighbour,
+                )));
+            }
+        }
+
+        None
+    }
+
+    /// Finds a minimum cut between the source and the sink of an undirected graph, whose edges
+    /// all have a capacity of 1 whatever their weight. This is the Edmonds-Karp algorithm: the
+    /// size of the cut is the number of paths found from the source to the sink that don't share
+    /// an edge, and the source side is what remains reachable from the source once they are used.
+    ///
+    /// Panics if the source is the sink.
+    fn min_cut(&self, source: Self::Node, sink: Self::Node) -> MinCut<Self::Node> {
+        assert!(source != sink, "the source must not be the sink");
+
+        // The flow going through each edge taken by a path, which is opposite the other way.
+        let mut flows: HashMap<(Self::Node, Self::Node), i32> = HashMap::new();
+        let mut size = 0;
+        loop {
+            // Find the shortest path through the edges which can still take some flow.
+            let mut previous_nodes: HashMap<_, _> = HashMap::from_iter([(source, source)]);
+            let mut to_visit = VecDeque::from([source]);
+            while let Some(node) = to_visit.pop_front() {
+                if node == sink {
+                    break;
+                }
+
+                for (neighbour, _) in self.edges(node) {
+                    let flow = flows.get(&(node, neighbour)).copied().unwrap_or_default();
+                    if flow < 1 && !previous_nodes.contains_key(&neighbour) {
+                        previous_nodes.insert(neighbour, node);
+                        to_visit.push_back(neighbour);
+                    }
+                }
+            }
+
+            if !previous_nodes.contains_key(&sink) {
+                return MinCut {
+                    size,
+                    source_side: previous_nodes.into_keys().collect(),
+                };
+            }
+
+            // Walk the path back to make it take some flow.
+            let mut node = sink;
+            while node != source {
+                let previous_node = previous_nodes[&node];
+                *flows.entry((previous_node, node)).or_default() += 1;
+                *flows.entry((node, previous_node)).or_default() -= 1;
+                node = previous_node;
+            }
+            size += 1;
+        }
+    }
+
+    /// Contracts the paths between the given nodes into single edges, weighted by the total
+    /// weight of the path. The node `i` of the contracted graph is `nodes[i]`.
+    ///
+    /// The other nodes of the paths must be corridors, with a way in and at most a way out that
+    /// doesn't go back. Paths leading to a dead end are dropped.
+    fn contract(&self, nodes: &[Self::Node]) -> AdjacencyList {
+        let ids: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(id, node)| (*node, id))
+            .collect();
+        let mut graph = AdjacencyList::with_nodes(nodes.len());
+
+        for (id, node) in nodes.iter().enumerate() {
+            for (mut next, mut weight) in self.edges(*node) {
+                let mut visited: HashSet<_> = HashSet::from_iter([*node, next]);
+                while !ids.contains_key(&next) {
+                    let Some((following, edge_weight)) = self
+                        .edges(next)
+                        .find(|(following, _)| !visited.contains(following))
+                    else {
+                        break;
+                    };
+
+                    visited.insert(following);
+                    next = following;
+                    weight += edge_weight;
+                }
+
+                if let Some(next_id) = ids.get(&next) {
+                    graph.add_edge(id, *next_id, weight);
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+/// A graph whose nodes are numbered from 0, so they can be tracked in a vector rather than a set.
+pub trait IndexedGraph: Graph<Node = usize> {
+    fn num_nodes(&self) -> usize;
+
+    /// Returns the weight of the heaviest path from the start to the goal which doesn't go twice
+    /// through the same node, or `None` if the goal can't be reached. Every such path is
+    /// explored, which is only practical on small graphs.
+    fn longest_path(&self, start: usize, goal: usize) -> Option<u64> {
+        if start == goal {
+            return Some(0);
+        }
+
+        // If the goal can only be reached from a single node, a path must go to the goal once it
+        // reaches that node: going anywhere else, it could never come back.
+        let mut last_edges = HashMap::new();
+        for node in 0..self.num_nodes() {
+            for (neighbour, weight) in self.edges(node) {
+                if neighbour == goal && node != goal {
+                    let last_weight = last_edges.entry(node).or_insert(weight);
+                    *last_weight = weight.max(*last_weight);
+                }
+            }
+        }
+
+        let mut visited = vec![false; self.num_nodes()];
+        if last_edges.len() == 1 {
+            let (last_node, last_weight) = last_edges.into_iter().next().unwrap();
+            visited[goal] = true;
+            longest_path_from(self, start, last_node, &mut visited)
+                .map(|weight| weight + last_weight)
+        } else {
+            longest_path_from(self, start, goal, &mut visited)
+        }
+    }
+}
+
+fn longest_path_from<G: IndexedGraph + ?Sized>(
+    graph: &G,
+    node: usize,
+    goal: usize,
+    visited: &mut [bool],
+) -> Option<u64> {
+    if node == goal {
+        return Some(0);
+    }
+
+    visited[node] = true;
+    let mut longest_path = None;
+    for (neighbour, weight) in graph.edges(node) {
+        if visited[neighbour] {
+            continue;
+        }
+
+        let path = longest_path_from(graph, neighbour, goal, visited).map(|path| path + weight);
+        longest_path = longest_path.max(path);
+    }
+    visited[node] = false;
+
+    longest_path
+}
+
+/// A cut splitting a graph in two.
+#[derive(Debug)]
+pub struct MinCut<N> {
+    /// The number of edges cut.
+    pub size: usize,
+    pub source_side: HashSet<N>,
+}
+
+/// A graph storing the edges leaving each of its nodes, which are numbered from 0.
+#[derive(Clone, Debug, Default)]
+pub struct AdjacencyList {
+    edges: Vec<Vec<(usize, u64)>>,
+}
+
+impl AdjacencyList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a graph of `num_nodes` nodes without any edge.
+    pub fn with_nodes(num_nodes: usize) -> Self {
+        Self {
+            edges: vec![Vec::new(); num_nodes],
+        }
+    }
+
+    /// Adds a node without any edge, and returns it.
+    pub fn add_node(&mut self) -> usize {
+        self.edges.push(Vec::new());
+        self.edges.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u64) {
+        self.edges[from].push((to, weight));
+    }
+
+    pub fn add_undirected_edge(&mut self, first: usize, second: usize, weight: u64) {
+        self.add_edge(first, second, weight);
+        self.add_edge(second, first, weight);
+    }
+}
+
+impl Graph for AdjacencyList {
+    type Node = usize;
+
+    fn edges(&self, node: usize) -> impl Iterator<Item = (usize, u64)> {
+        self.edges[node].iter().copied()
+    }
+}
+
+impl IndexedGraph for AdjacencyList {
+    fn num_nodes(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two squares 0-1-2-3 and 4-5-6-7, linked by the edges 2-4 and 3-5. The edge 0-2 is a
+    // diagonal, and 8 is a dead end.
+    fn two_squares() -> AdjacencyList {
+        let mut graph = AdjacencyList::with_nodes(9);
+        for (first, second, weight) in [
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+            (3, 0, 1),
+            (0, 2, 5),
+            (2, 4, 1),
+            (3, 5, 4),
+            (4, 5, 1),
+            (5, 6, 1),
+            (6, 7, 1),
+            (7, 4, 1),
+            (7, 8, 1),
+        ] {
+            graph.add_undirected_edge(first, second, weight);
+        }
+
+        graph
+    }
+
+    #[test]
+    fn breadth_first_search_counts_edges() {
+        let graph = two_squares();
+        let reached = graph.breadth_first_search(0);
+
+        assert_eq!(reached.len(), 9);
+        assert_eq!(reached[0], (0, 0));
+        assert_eq!(reached.last(), Some(&(8, 4)));
+    }
+
+    #[test]
+    fn shortest_path_sums_weights() {
+        let graph = two_squares();
+
+        assert_eq!(graph.shortest_path([0], |node| node == 5), Some(4));
+        assert_eq!(graph.shortest_path([8, 1], |node| node == 3), Some(2));
+        assert_eq!(graph.a_star([0], |node| node == 8, |_| 1), Some(5));
+        assert_eq!(graph.shortest_path([0], |node| node == 9), None);
+    }
+
+    #[test]
+    fn longest_path_visits_nodes_once() {
+        let graph = two_squares();
+
+        // 0-2-3-5-4-7-8, going through the heavy diagonal.
+        assert_eq!(graph.longest_path(0, 8), Some(13));
+        assert_eq!(graph.longest_path(8, 8), Some(0));
+        assert_eq!(AdjacencyList::with_nodes(2).longest_path(0, 1), None);
+    }
+
+    #[test]
+    fn min_cut_separates_the_squares() {
+        let graph = two_squares();
+        let cut = graph.min_cut(0, 6);
+
+        assert_eq!(cut.size, 2);
+        assert_eq!(cut.source_side, HashSet::from([0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn contraction_skips_corridors() {
+        let graph = two_squares();
+        // Only 1 and 6 have two neighbours.
+        let contracted = graph.contract(&[0, 2, 3, 4, 5, 7, 8]);
+
+        let mut edges: Vec<_> = contracted.edges(4).collect();
+        edges.sort();
+        assert_eq!(edges, [(2, 4), (3, 1), (5, 2)]);
+    }
+}
@@ -0,0 +1,338 @@
+//! A rectangular grid of cells, as found in the many puzzles whose input is a map.
+
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Point, Position};
+use crate::parse::{self, ParseError, ParseResult};
+
+// The offsets of the 8 neighbours, including the diagonal ones, in reading order.
+const NEIGHBOURS8: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A rectangular grid, whose cells are stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    num_rows: usize,
+    num_cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, row by row.
+    ///
+    /// Panics if there isn't exactly `num_rows * num_cols` cells.
+    pub fn new(num_rows: usize, num_cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            num_rows * num_cols,
+            "the grid isn't rectangular"
+        );
+
+        Self {
+            cells,
+            num_rows,
+            num_cols,
+        }
+    }
+
+    /// Creates a grid whose cells are all `value`.
+    pub fn filled(num_rows: usize, num_cols: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(num_rows, num_cols, vec![value; num_rows * num_cols])
+    }
+
+    /// Parses a grid with a cell per character, e.g. a map of tiles.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        parse_cell: impl Fn(char) -> Option<T>,
+    ) -> ParseResult<Self> {
+        Self::parse_rows(input.lines(), |_, line| {
+            parse::chars(line, expected, &parse_cell).collect()
+        })
+    }
+
+    /// Parses a grid with a row per line, for rows that aren't simply a cell per character.
+    /// All rows must have as many cells as the first one, and a grid must have a cell.
+    pub fn parse_rows<'a>(
+        lines: impl Iterator<Item = &'a str>,
+        mut parse_row: impl FnMut(usize, &'a str) -> ParseResult<Vec<T>>,
+    ) -> ParseResult<Self> {
+        let mut cells = Vec::new();
+        let mut num_rows = 0;
+        let mut num_cols = None;
+        let mut first_line = "";
+        for line in lines {
+            if num_rows == 0 {
+                first_line = line;
+            }
+            let row = parse_row(num_rows, line)?;
+            let num_cols = *num_cols.get_or_insert(row.len());
+            if row.len() != num_cols {
+                return Err(ParseError::new(line, format!("a row of {num_cols} cells")));
+            }
+
+            cells.extend(row);
+            num_rows += 1;
+        }
+
+        if cells.is_empty() {
+            return Err(ParseError::new(first_line, "a grid with a cell"));
+        }
+
+        Ok(Self::new(num_rows, num_cols.unwrap_or(0), cells))
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.row < self.num_rows && position.col < self.num_cols
+    }
+
+    /// Returns the cell at the position, or `None` if it is beyond the grid.
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.row * self.num_cols + position.col])
+    }
+
+    /// Returns the cell at the position, or `None` if it is beyond the grid.
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.row * self.num_cols + position.col])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the position moved by the offset, or `None` if it is beyond the grid.
+    pub fn offset(&self, position: Position, offset: Point) -> Option<Position> {
+        position
+            .checked_add(offset)
+            .filter(|position| self.contains(*position))
+    }
+
+    /// Returns the position a step away in the direction, or `None` if it is beyond the grid.
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        self.offset(position, direction.offset())
+    }
+
+    /// Returns the position of a grid repeating itself infinitely in every direction, once
+    /// brought back into this grid.
+    pub fn wrap(&self, point: Point) -> Position {
+        Position::new(
+            point.row.rem_euclid(self.num_rows as i64) as usize,
+            point.col.rem_euclid(self.num_cols as i64) as usize,
+        )
+    }
+
+    /// Returns the cell at the point of a grid repeating itself infinitely in every direction.
+    pub fn get_wrapping(&self, point: Point) -> &T {
+        &self[self.wrap(point)]
+    }
+
+    /// Returns the positions of the orthogonal neighbours within the grid, clockwise from the
+    /// one above.
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    /// Returns the positions of the neighbours within the grid, including the diagonal ones, in
+    /// reading order.
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .filter_map(move |(d_row, d_col)| self.offset(position, Point::new(d_row, d_col)))
+    }
+
+    /// Returns all the positions of the grid, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let num_cols = self.num_cols;
+        (0..self.num_rows)
+            .flat_map(move |row| (0..num_cols).map(move |col| Position::new(row, col)))
+    }
+
+    /// Returns the cells with their position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Returns the cells with their position, in reading order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Position, &mut T)> {
+        self.positions().zip(&mut self.cells)
+    }
+
+    /// Returns the position of the first cell matching the predicate, in reading order.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.num_cols..(row + 1) * self.num_cols]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.num_rows).map(|row| self.row(row))
+    }
+
+    /// Creates a grid of the same size, whose cells are mapped from this grid's cells.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(
+            self.num_rows,
+            self.num_cols,
+            self.cells.iter().map(f).collect(),
+        )
+    }
+
+    /// Creates a grid of `num_rows` by `num_cols`, whose cells are copied from this grid at the
+    /// position returned by `from`.
+    fn rearrange(
+        &self,
+        num_rows: usize,
+        num_cols: usize,
+        from: impl Fn(usize, usize) -> Position,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..num_rows)
+            .flat_map(|row| (0..num_cols).map(move |col| (row, col)))
+            .map(|(row, col)| self[from(row, col)].clone())
+            .collect();
+
+        Self::new(num_rows, num_cols, cells)
+    }
+
+    /// Swaps the rows and the columns of the grid.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.num_cols, self.num_rows, |row, col| {
+            Position::new(col, row)
+        })
+    }
+
+    /// Rotates the grid by a quarter turn clockwise: the first column becomes the first row.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.num_cols, self.num_rows, |row, col| {
+            Position::new(self.num_rows - 1 - col, row)
+        })
+    }
+
+    /// Rotates the grid by a quarter turn counterclockwise: the first row becomes the first
+    /// column.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rearrange(self.num_cols, self.num_rows, |row, col| {
+            Position::new(col, self.num_cols - 1 - row)
+        })
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is beyond the grid of {} by {}",
+                self.num_rows, self.num_cols
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (num_rows, num_cols) = (self.num_rows, self.num_cols);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!("{position:?} is beyond the grid of {num_rows} by {num_cols}")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(input, "a digit", |digit| digit.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn rows_must_have_the_same_length() {
+        let input = "123\n45\n";
+        let error = Grid::parse(input, "a digit", |digit| digit.to_digit(10))
+            .unwrap_err()
+            .locate(1, input);
+
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a row of 3 cells");
+    }
+
+    #[test]
+    fn grid_must_have_a_cell() {
+        for input in ["", "\n\n"] {
+            let error = Grid::parse(input, "a digit", |digit| digit.to_digit(10)).unwrap_err();
+            assert_eq!(error.expected, "a grid with a cell");
+        }
+    }
+
+    #[test]
+    fn neighbours_are_within_the_grid() {
+        let grid = digits("123\n456\n");
+
+        let neighbours4: Vec<_> = grid.neighbours4(Position::new(0, 0)).collect();
+        assert_eq!(neighbours4, [Position::new(0, 1), Position::new(1, 0)]);
+
+        let neighbours8: Vec<_> = grid
+            .neighbours8(Position::new(1, 1))
+            .map(|position| grid[position])
+            .collect();
+        assert_eq!(neighbours8, [1, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn wrapping_repeats_the_grid() {
+        let grid = digits("123\n456\n");
+
+        assert_eq!(*grid.get_wrapping(Point::new(-1, -1)), 6);
+        assert_eq!(*grid.get_wrapping(Point::new(2, 4)), 2);
+        assert_eq!(grid.get(Position::new(2, 0)), None);
+    }
+
+    #[test]
+    fn rotations_and_transposition() {
+        let grid = digits("123\n456\n");
+
+        assert_eq!(grid.transpose(), digits("14\n25\n36\n"));
+        assert_eq!(grid.rotate_clockwise(), digits("41\n52\n63\n"));
+        assert_eq!(grid.rotate_counterclockwise(), digits("36\n25\n14\n"));
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+}
@@ -0,0 +1,176 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use advent_of_code_2023::registry::SolverEntry;
+use advent_of_code_2023::ParseError;
+use serde_json::{json, Value};
+
+use crate::runner;
+
+// Where an input imported for the day is stored in the inputs directory.
+pub fn imported_path(inputs_dir: &Path, year: u16, day: u8) -> PathBuf {
+    inputs_dir
+        .join(year.to_string())
+        .join(format!("day{day}.txt"))
+}
+
+// Where the answers to the day's imported input are recorded, next to it in the inputs directory.
+pub fn answers_path(inputs_dir: &Path, year: u16, day: u8) -> PathBuf {
+    inputs_dir
+        .join(year.to_string())
+        .join(format!("day{day}.answers.toml"))
+}
+
+// The input imported for the day, if any.
+pub fn default_path(inputs_dir: &Path, year: u16, day: u8) -> Option<PathBuf> {
+    let imported = imported_path(inputs_dir, year, day);
+    imported.is_file().then_some(imported)
+}
+
+// Reads the input from the given path, or from stdin if the path is `-`. Without a path, falls
+// back to the day's default input.
+pub fn read_input(
+    inputs_dir: &Path,
+    year: u16,
+    day: u8,
+    path: Option<&Path>,
+) -> io::Result<String> {
+    match path {
+        Some(path) if path == Path::new("-") => io::read_to_string(io::stdin()),
+        Some(path) => std::fs::read_to_string(path),
+        None => match default_path(inputs_dir, year, day) {
+            Some(path) => std::fs::read_to_string(path),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("there is no input for day {day} of {year}, import one or use --input"),
+            )),
+        },
+    }
+}
+
+// The input is parsed by the day's parser without being solved, so that validating all inputs is
+// quick.
+pub fn validate(solver: &SolverEntry, input: &str) -> Result<(), ParseError> {
+    solver.validate(input)
+}
+
+// Validates the input, then copies it to the inputs directory. An input already imported for
+// the day is only replaced if `force` is set.
+pub fn import(
+    inputs_dir: &Path,
+    solver: &SolverEntry,
+    input: &str,
+    force: bool,
+) -> Result<PathBuf, String> {
+    let path = imported_path(inputs_dir, solver.year, solver.day);
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+        ));
+    }
+    validate(solver, input).map_err(|error| error.to_string())?;
+
+    let write = || {
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, input)
+    };
+    write().map_err(|error| format!("could not write {}: {error}", path.display()))?;
+
+    Ok(path)
+}
+
+// The default input of a day, and whether it is valid once it has been validated.
+pub struct Input {
+    pub day: u8,
+    pub path: Option<PathBuf>,
+    pub validation: Option<Result<(), String>>,
+}
+
+impl Input {
+    pub fn new(inputs_dir: &Path, solver: &SolverEntry) -> Self {
+        Self {
+            day: solver.day,
+            path: default_path(inputs_dir, solver.year, solver.day),
+            validation: None,
+        }
+    }
+
+    // Days without an input are reported, but aren't invalid.
+    pub fn validate(&mut self, solver: &SolverEntry) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        self.validation = Some(match std::fs::read_to_string(path) {
+            Ok(input) => validate(solver, &input).map_err(|error| error.to_string()),
+            Err(error) => Err(format!("could not read {}: {error}", path.display())),
+        });
+    }
+
+    pub fn invalid(&self) -> bool {
+        matches!(self.validation, Some(Err(_)))
+    }
+
+    // Days without an input have a null `path`, and inputs which weren't validated a null
+    // `valid`.
+    pub fn to_json(&self) -> Value {
+        let mut input = json!({
+            "day": self.day,
+            "path": self.path.as_ref().map(|path| path.display().to_string()),
+        });
+        match &self.validation {
+            Some(Ok(())) => input["valid"] = json!(true),
+            Some(Err(error)) => {
+                input["valid"] = json!(false);
+                input["error"] = json!(error);
+            }
+            None => {}
+        }
+
+        input
+    }
+
+    fn path_cell(&self) -> String {
+        self.path
+            .as_ref()
+            .map_or("-".to_owned(), |path| path.display().to_string())
+    }
+
+    fn result_cell(&self) -> String {
+        match &self.validation {
+            _ if self.path.is_none() => "no input".to_owned(),
+            Some(Ok(())) => "valid".to_owned(),
+            Some(Err(error)) => format!("INVALID: {error}"),
+            None => "-".to_owned(),
+        }
+    }
+}
+
+pub fn print_inputs(inputs: &[Input]) {
+    let rows: Vec<_> = inputs
+        .iter()
+        .map(|input| [input.day.to_string(), input.path_cell()])
+        .collect();
+    runner::print_table(["Day", "Input"], &rows, &[1]);
+}
+
+pub fn print_validations(inputs: &[Input]) {
+    let rows: Vec<_> = inputs
+        .iter()
+        .map(|input| {
+            [
+                input.day.to_string(),
+                input.path_cell(),
+                input.result_cell(),
+            ]
+        })
+        .collect();
+    runner::print_table(["Day", "Input", "Result"], &rows, &[1, 2]);
+
+    let invalid = inputs.iter().filter(|input| input.invalid()).count();
+    let validated = inputs
+        .iter()
+        .filter(|input| input.validation.is_some())
+        .count();
+    println!("\n{invalid} of {validated} inputs are invalid.");
+}
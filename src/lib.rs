@@ -0,0 +1,107 @@
+//! Solutions to the 2023 edition of Advent of Code.
+//!
+//! Each day lives in its own module, which exposes a `DayNSolver` implementing [`Solver`] as
+//! well as the parsers and building blocks used to solve that day's problem. Building blocks
+//! shared by several days, such as the [`grid`] of the many puzzles whose input is a map, the
+//! [`geometry`] of moving around it, the [`graph`] searches, or the [`math`] and [`cycle`] of
+//! periodic events, have their own modules. Solvers [`trace`](mod@trace) their intermediate state
+//! to help debugging them.
+
+#![cfg_attr(all(test, feature = "nightly"), feature(test))]
+#[cfg(all(test, feature = "nightly"))]
+extern crate test;
+
+pub mod cycle;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod parse;
+pub mod registry;
+pub mod solver;
+pub mod trace;
+
+use std::path::PathBuf;
+
+pub use parse::ParseError;
+pub use registry::Registry;
+pub use solver::{Answer, Part, Solver};
+
+/// The default directory of the puzzle inputs, kept per user as `<DIR>/<YEAR>/dayN.txt`:
+/// `$XDG_DATA_HOME/advent-of-code`, or `~/.local/share/advent-of-code`. Falls back to `inputs` in
+/// the current directory when there is no home directory.
+pub fn default_inputs_dir() -> PathBuf {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")));
+    data_home.map_or_else(|| PathBuf::from("inputs"), |dir| dir.join("advent-of-code"))
+}
+
+// Reads the input imported for the day, which the benchmarks solve. Like the CLI, the inputs
+// directory can be changed with `AOC_INPUTS_DIR`.
+#[cfg(all(test, feature = "nightly"))]
+fn bench_input(day: u8) -> String {
+    let inputs_dir =
+        std::env::var_os("AOC_INPUTS_DIR").map_or_else(default_inputs_dir, PathBuf::from);
+    let path = inputs_dir.join("2023").join(format!("day{day}.txt"));
+    std::fs::read_to_string(path).unwrap()
+}
+
+/// Builds the registry of all the solvers in this crate.
+pub fn registry() -> Registry {
+    let mut registry = Registry::default();
+    registry
+        .register::<day1::Day1Solver>()
+        .register::<day2::Day2Solver>()
+        .register::<day3::Day3Solver>()
+        .register::<day4::Day4Solver>()
+        .register::<day5::Day5Solver>()
+        .register::<day6::Day6Solver>()
+        .register::<day7::Day7Solver>()
+        .register::<day8::Day8Solver>()
+        .register::<day9::Day9Solver>()
+        .register::<day10::Day10Solver>()
+        .register::<day11::Day11Solver>()
+        .register::<day12::Day12Solver>()
+        .register::<day13::Day13Solver>()
+        .register::<day14::Day14Solver>()
+        .register::<day15::Day15Solver>()
+        .register::<day16::Day16Solver>()
+        .register::<day17::Day17Solver>()
+        .register::<day18::Day18Solver>()
+        .register::<day19::Day19Solver>()
+        .register::<day20::Day20Solver>()
+        .register::<day21::Day21Solver>()
+        .register::<day22::Day22Solver>()
+        .register::<day23::Day23Solver>()
+        .register::<day24::Day24Solver>()
+        .register::<day25::Day25Solver>();
+
+    registry
+}
@@ -1,95 +1,367 @@
-#![feature(test)]
-#![feature(variant_count)]
-#[macro_use]
-extern crate lazy_static;
-extern crate test;
-
+use advent_of_code_2023::day1::{Day1Solver, Scanner};
+use advent_of_code_2023::day2::{Bag, Day2Solver, Game};
+use advent_of_code_2023::registry::{Registry, SolverEntry};
+use advent_of_code_2023::{Part, Solver};
+use args::{Args, BenchArgs, Command, Format, InputsCommand};
+use bench::Bench;
 use clap::Parser;
+use inputs::Input;
+use runner::Run;
+use serde_json::{json, Value};
+use std::time::Instant;
+use verify::Verification;
 
+mod allocations;
 mod args;
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod solver;
-
-use args::{Args, Day};
-use day1::Day1Solver;
-use day10::Day10Solver;
-use day11::Day11Solver;
-use day12::Day12Solver;
-use day13::Day13Solver;
-use day14::Day14Solver;
-use day15::Day15Solver;
-use day16::Day16Solver;
-use day17::Day17Solver;
-use day18::Day18Solver;
-use day19::Day19Solver;
-use day2::Day2Solver;
-use day20::Day20Solver;
-use day21::Day21Solver;
-use day22::Day22Solver;
-use day23::Day23Solver;
-use day24::Day24Solver;
-use day25::Day25Solver;
-use day3::Day3Solver;
-use day4::Day4Solver;
-use day5::Day5Solver;
-use day6::Day6Solver;
-use day7::Day7Solver;
-use day8::Day8Solver;
-use day9::Day9Solver;
-use solver::Solver;
+mod bench;
+mod calibrate;
+mod cubes;
+mod inputs;
+mod runner;
+mod verify;
+
+#[global_allocator]
+static ALLOCATOR: allocations::CountingAllocator = allocations::CountingAllocator;
+
+// The solver of the day, or of every day of the year. Exits with an error if there are none.
+fn solvers_or_exit(registry: &Registry, year: u16, day: Option<u8>) -> Vec<&SolverEntry> {
+    let solvers: Vec<_> = match day {
+        Some(day) => registry.get(year, day).into_iter().collect(),
+        None => registry.iter_year(year).collect(),
+    };
+    if solvers.is_empty() {
+        match day {
+            Some(day) => eprintln!("There is no solver for day {day} of {year}."),
+            None => eprintln!("There are no solvers for {year}."),
+        }
+        std::process::exit(1);
+    }
+
+    solvers
+}
+
+// Reads the input of the day, like `read_input`. Exits with an error if it can't be read.
+fn input_or_exit(cli: &Args, year: u16, day: u8) -> String {
+    match inputs::read_input(&cli.inputs_dir, year, day, cli.input.as_deref()) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("Could not read the input of day {day}: {error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_all(cli: &Args, registry: &Registry, jobs: u32) {
+    if cli.input.is_some() {
+        eprintln!("The --input option cannot be used when solving all days.");
+        std::process::exit(1);
+    }
+
+    let solvers: Vec<_> = solvers_or_exit(registry, cli.year, None)
+        .into_iter()
+        .map(|solver| {
+            let input = inputs::read_input(&cli.inputs_dir, cli.year, solver.day, None);
+            (solver, input)
+        })
+        .collect();
+    let start = Instant::now();
+    let runs = runner::run_parallel(&solvers, &[Part::Part1, Part::Part2], jobs as usize);
+    match cli.format {
+        Format::Text => {
+            runner::print_runs(&runs);
+            if jobs > 1 {
+                println!("Wall time with {jobs} jobs: {:.2?}", start.elapsed());
+            }
+        }
+        Format::Json => println!("{}", Value::from_iter(runs.iter().map(Run::to_json))),
+    }
+    if runs.iter().any(Run::invalid_input) {
+        std::process::exit(1);
+    }
+}
+
+fn verify(cli: &Args, registry: &Registry, day: Option<u8>) {
+    if cli.input.is_some() {
+        eprintln!("The --input option cannot be used when verifying answers.");
+        std::process::exit(1);
+    }
+
+    let solvers = solvers_or_exit(registry, cli.year, day);
+    let verifications: Vec<_> = solvers
+        .into_iter()
+        .flat_map(|solver| {
+            let input = inputs::read_input(&cli.inputs_dir, cli.year, solver.day, None);
+            let answers = verify::read_answers(&cli.inputs_dir, cli.year, solver.day);
+            [Part::Part1, Part::Part2]
+                .map(|part| Verification::new(runner::run(solver, part, &input), &answers))
+        })
+        .collect();
+    match cli.format {
+        Format::Text => verify::print_verifications(&verifications),
+        Format::Json => println!(
+            "{}",
+            Value::from_iter(verifications.iter().map(Verification::to_json))
+        ),
+    }
+    if verifications
+        .iter()
+        .any(|verification| verification.failed())
+    {
+        std::process::exit(1);
+    }
+}
+
+fn bench(cli: &Args, registry: &Registry, args: &BenchArgs) {
+    if cli.input.is_some() && args.day.is_none() {
+        eprintln!("The --input option requires --day when benchmarking.");
+        std::process::exit(1);
+    }
+
+    let baseline = args
+        .baseline
+        .as_ref()
+        .map(|path| match bench::read_baseline(path) {
+            Ok(baseline) => baseline,
+            Err(error) => {
+                eprintln!("Could not read the baseline {}: {error}", path.display());
+                std::process::exit(1);
+            }
+        });
+
+    let solvers = solvers_or_exit(registry, cli.year, args.day);
+    let benches: Vec<_> = solvers
+        .into_iter()
+        .flat_map(|solver| {
+            let input =
+                inputs::read_input(&cli.inputs_dir, cli.year, solver.day, cli.input.as_deref());
+            args.part
+                .parts()
+                .iter()
+                .map(|part| bench::bench(solver, *part, &input, args.runs as usize))
+                .collect::<Vec<_>>()
+        })
+        .map(|mut bench| {
+            if let Some(baseline) = &baseline {
+                let key = (bench.run.day, bench.run.part.number());
+                bench.baseline_median = baseline.get(&key).copied();
+            }
+            bench
+        })
+        .collect();
+
+    let json = Value::from_iter(benches.iter().map(Bench::to_json));
+    if let Some(path) = &args.save {
+        if let Err(error) = std::fs::write(path, format!("{json}\n")) {
+            eprintln!("Could not save the baseline {}: {error}", path.display());
+            std::process::exit(1);
+        }
+    }
+    match cli.format {
+        Format::Text => bench::print_benches(&benches, args.threshold),
+        Format::Json => println!("{json}"),
+    }
+    if benches.iter().any(|bench| bench.regressed(args.threshold)) {
+        std::process::exit(1);
+    }
+}
+
+fn calibrate(cli: &Args, vocabulary: &str, explain: bool) {
+    let vocabulary = match calibrate::read_vocabulary(vocabulary) {
+        Ok(vocabulary) => vocabulary,
+        Err(error) => {
+            eprintln!("Could not read the vocabulary {vocabulary}: {error}");
+            std::process::exit(1);
+        }
+    };
+    let input = input_or_exit(cli, Day1Solver::YEAR, Day1Solver::DAY);
+
+    let scanner = Scanner::new(vocabulary);
+    if explain {
+        let calibrations = calibrate::explain(&input, &scanner);
+        match cli.format {
+            Format::Text => calibrate::print_explanations(&calibrations),
+            Format::Json => println!(
+                "{}",
+                Value::from_iter(
+                    (1..)
+                        .zip(&calibrations)
+                        .map(|(line, calibration)| calibrate::to_json(line, calibration))
+                )
+            ),
+        }
+        if calibrations.iter().any(Option::is_none) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    match Day1Solver::solve_problem(&input, &scanner) {
+        Ok(sum) => match cli.format {
+            Format::Text => println!("{sum}"),
+            Format::Json => println!("{}", json!({ "answer": sum })),
+        },
+        Err(error) => {
+            eprintln!("{}", error.locate(Day1Solver::DAY, &input));
+            std::process::exit(1);
+        }
+    }
+}
+
+fn cubes(cli: &Args, bag: &str, minimal_bags: bool) {
+    let input = input_or_exit(cli, Day2Solver::YEAR, Day2Solver::DAY);
+    if minimal_bags {
+        let games = match input
+            .lines()
+            .map(Game::parse)
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(games) => games,
+            Err(error) => {
+                eprintln!("{}", error.locate(Day2Solver::DAY, &input));
+                std::process::exit(1);
+            }
+        };
+        match cli.format {
+            Format::Text => cubes::print_minimal_bags(&games),
+            Format::Json => println!("{}", Value::from_iter(games.iter().map(cubes::to_json))),
+        }
+        return;
+    }
+
+    let bag = match Bag::parse(bag) {
+        Ok(bag) => bag,
+        Err(error) => {
+            let error = error.locate(Day2Solver::DAY, bag);
+            eprintln!(
+                "Invalid bag at column {}: expected {}, found `{}`",
+                error.column, error.expected, error.text
+            );
+            std::process::exit(1);
+        }
+    };
+
+    match Day2Solver::sum_possible_game_ids(&input, &bag) {
+        Ok(sum) => match cli.format {
+            Format::Text => println!("{sum}"),
+            Format::Json => println!("{}", json!({ "answer": sum })),
+        },
+        Err(error) => {
+            eprintln!("{}", error.locate(Day2Solver::DAY, &input));
+            std::process::exit(1);
+        }
+    }
+}
+
+fn inputs(cli: &Args, registry: &Registry, command: &InputsCommand) {
+    if cli.input.is_some() {
+        eprintln!("The --input option cannot be used when managing inputs.");
+        std::process::exit(1);
+    }
+
+    match command {
+        InputsCommand::List => {
+            let solvers = solvers_or_exit(registry, cli.year, None);
+            let inputs: Vec<_> = solvers
+                .into_iter()
+                .map(|solver| Input::new(&cli.inputs_dir, solver))
+                .collect();
+            match cli.format {
+                Format::Text => inputs::print_inputs(&inputs),
+                Format::Json => {
+                    println!("{}", Value::from_iter(inputs.iter().map(Input::to_json)))
+                }
+            }
+        }
+        InputsCommand::Import { day, path, force } => {
+            let day = *day;
+            let solver = solvers_or_exit(registry, cli.year, Some(day))[0];
+            let input = match inputs::read_input(&cli.inputs_dir, cli.year, day, Some(path)) {
+                Ok(input) => input,
+                Err(error) => {
+                    eprintln!("Could not read the input of day {day}: {error}");
+                    std::process::exit(1);
+                }
+            };
+            match inputs::import(&cli.inputs_dir, solver, &input, *force) {
+                Ok(path) => println!("Imported the input of day {day} to {}.", path.display()),
+                Err(error) => {
+                    eprintln!("Could not import the input of day {day}: {error}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        InputsCommand::Validate { day } => {
+            let solvers = solvers_or_exit(registry, cli.year, *day);
+            let inputs: Vec<_> = solvers
+                .into_iter()
+                .map(|solver| {
+                    let mut input = Input::new(&cli.inputs_dir, solver);
+                    input.validate(solver);
+                    input
+                })
+                .collect();
+            match cli.format {
+                Format::Text => inputs::print_validations(&inputs),
+                Format::Json => {
+                    println!("{}", Value::from_iter(inputs.iter().map(Input::to_json)))
+                }
+            }
+            if inputs.iter().any(Input::invalid) {
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+// Solves the parts of the day given with `--day`, which clap requires without a subcommand.
+fn solve_day(cli: &Args, registry: &Registry) {
+    let Some(day) = cli.day else {
+        eprintln!("The --day option is required without a subcommand.");
+        std::process::exit(1);
+    };
+    let solver = solvers_or_exit(registry, cli.year, Some(day))[0];
+    let input = input_or_exit(cli, cli.year, day);
+
+    match (cli.part.parts(), cli.format) {
+        ([part], Format::Text) => match solver.solve(*part, &input) {
+            Ok(answer) => println!("{answer}"),
+            Err(error) => {
+                eprintln!("{error}");
+                std::process::exit(1);
+            }
+        },
+        (parts, format) => {
+            let input = Ok(input);
+            let runs: Vec<_> = parts
+                .iter()
+                .map(|part| runner::run(solver, *part, &input))
+                .collect();
+            match format {
+                Format::Text => runner::print_runs(&runs),
+                Format::Json => runner::print_json_lines(&runs),
+            }
+            if runs.iter().any(Run::invalid_input) {
+                std::process::exit(1);
+            }
+        }
+    }
+}
 
 fn main() {
     let cli = Args::parse();
+    let registry = advent_of_code_2023::registry();
+    advent_of_code_2023::trace::set_enabled(cli.trace);
 
-    match cli.day {
-        Day::Day1 { part } => Day1Solver::solve(part),
-        Day::Day2 { part } => Day2Solver::solve(part),
-        Day::Day3 { part } => Day3Solver::solve(part),
-        Day::Day4 { part } => Day4Solver::solve(part),
-        Day::Day5 { part } => Day5Solver::solve(part),
-        Day::Day6 { part } => Day6Solver::solve(part),
-        Day::Day7 { part } => Day7Solver::solve(part),
-        Day::Day8 { part } => Day8Solver::solve(part),
-        Day::Day9 { part } => Day9Solver::solve(part),
-        Day::Day10 { part } => Day10Solver::solve(part),
-        Day::Day11 { part } => Day11Solver::solve(part),
-        Day::Day12 { part } => Day12Solver::solve(part),
-        Day::Day13 { part } => Day13Solver::solve(part),
-        Day::Day14 { part } => Day14Solver::solve(part),
-        Day::Day15 { part } => Day15Solver::solve(part),
-        Day::Day16 { part } => Day16Solver::solve(part),
-        Day::Day17 { part } => Day17Solver::solve(part),
-        Day::Day18 { part } => Day18Solver::solve(part),
-        Day::Day19 { part } => Day19Solver::solve(part),
-        Day::Day20 { part } => Day20Solver::solve(part),
-        Day::Day21 { part } => Day21Solver::solve(part),
-        Day::Day22 { part } => Day22Solver::solve(part),
-        Day::Day23 { part } => Day23Solver::solve(part),
-        Day::Day24 { part } => Day24Solver::solve(part),
-        Day::Day25 { part } => Day25Solver::solve(part),
+    match &cli.command {
+        Some(Command::All { jobs }) => run_all(&cli, &registry, *jobs),
+        Some(Command::Verify { day }) => verify(&cli, &registry, *day),
+        Some(Command::Bench(args)) => bench(&cli, &registry, args),
+        Some(Command::Calibrate {
+            vocabulary,
+            explain,
+        }) => calibrate(&cli, vocabulary, *explain),
+        Some(Command::Cubes { bag, minimal_bags }) => cubes(&cli, bag, *minimal_bags),
+        Some(Command::Inputs { command }) => inputs(&cli, &registry, command),
+        None => solve_day(&cli, &registry),
     }
 }
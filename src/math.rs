@@ -0,0 +1,169 @@
+//! Number theory, for the puzzles combining things which repeat with different periods.
+
+/// Returns the greatest common divisor of the two numbers, which is 0 only if both are 0.
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Returns the least common multiple of the two numbers, or `None` if it overflows.
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Returns the greatest common divisor of all the numbers, which is 0 if there are none.
+pub fn gcd_all(numbers: impl IntoIterator<Item = u64>) -> u64 {
+    numbers.into_iter().fold(0, gcd)
+}
+
+/// Returns the least common multiple of all the numbers, which is 1 if there are none, or `None`
+/// if it overflows.
+pub fn checked_lcm_all(numbers: impl IntoIterator<Item = u64>) -> Option<u64> {
+    numbers.into_iter().try_fold(1, checked_lcm)
+}
+
+/// Returns the greatest common divisor `g` of the two numbers, with the coefficients `x` and `y`
+/// of Bézout's identity `a * x + b * y = g`. This is the extended Euclidean algorithm.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns the inverse of the number modulo `modulus`, in `0..modulus`, or `None` if they aren't
+/// coprime.
+pub fn mod_inverse(number: i64, modulus: i64) -> Option<i64> {
+    let (gcd, x, _) = extended_gcd(number.rem_euclid(modulus), modulus);
+    (gcd == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// The numbers `x` such that `x ≡ residue (mod modulus)`, e.g. the times at which something
+/// repeating with a period of `modulus` happens, once it started at `residue`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Congruence {
+    /// The smallest non-negative solution.
+    pub residue: i64,
+    pub modulus: i64,
+}
+
+impl Congruence {
+    /// Panics if the modulus isn't positive.
+    pub fn new(residue: i64, modulus: i64) -> Self {
+        assert!(modulus > 0, "the modulus must be positive");
+
+        Self {
+            residue: residue.rem_euclid(modulus),
+            modulus,
+        }
+    }
+
+    pub fn contains(self, number: i64) -> bool {
+        number.rem_euclid(self.modulus) == self.residue
+    }
+
+    /// Returns the smallest solution which is at least `min`, e.g. the first time something
+    /// happens once it started repeating.
+    pub fn smallest_at_least(self, min: i64) -> i64 {
+        min + (self.residue - min).rem_euclid(self.modulus)
+    }
+
+    /// Returns the congruence whose solutions are common to both, or `None` if there are none, or
+    /// if its modulus overflows. The moduli don't need to be coprime.
+    pub fn combine(self, other: Congruence) -> Option<Congruence> {
+        let (gcd, x, _) = extended_gcd(self.modulus, other.modulus);
+        let difference = other.residue - self.residue;
+        if difference % gcd != 0 {
+            return None;
+        }
+
+        // Solve `self.residue + self.modulus * k ≡ other.residue (mod other.modulus)`, with `x`
+        // being the inverse of `self.modulus / gcd` modulo `other.modulus / gcd`.
+        let other_modulus = (other.modulus / gcd) as i128;
+        let k = (difference / gcd) as i128 * x as i128 % other_modulus;
+        let modulus = (self.modulus as i128) * other_modulus;
+        let residue = (self.residue as i128 + self.modulus as i128 * k).rem_euclid(modulus);
+
+        Some(Congruence::new(
+            residue.try_into().ok()?,
+            modulus.try_into().ok()?,
+        ))
+    }
+}
+
+/// Returns the congruence whose solutions are common to all of them, or `None` if there are none,
+/// or if its modulus overflows. This is the Chinese remainder theorem, generalized to moduli
+/// which aren't coprime.
+pub fn chinese_remainder(congruences: impl IntoIterator<Item = Congruence>) -> Option<Congruence> {
+    congruences
+        .into_iter()
+        .try_fold(Congruence::new(0, 1), Congruence::combine)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm_of_many_numbers() {
+        assert_eq!(gcd_all([12, 18, 30]), 6);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(checked_lcm_all([4, 6, 10]), Some(60));
+        assert_eq!(checked_lcm_all([]), Some(1));
+        assert_eq!(checked_lcm_all([u64::MAX, 2]), None);
+    }
+
+    #[test]
+    fn bezout_coefficients_and_inverse() {
+        let (gcd, x, y) = extended_gcd(240, 46);
+        assert_eq!(gcd, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 10), None);
+    }
+
+    #[test]
+    fn chinese_remainder_with_coprime_moduli() {
+        let congruences = [
+            Congruence::new(2, 3),
+            Congruence::new(3, 5),
+            Congruence::new(2, 7),
+        ];
+
+        assert_eq!(
+            chinese_remainder(congruences),
+            Some(Congruence::new(23, 105))
+        );
+    }
+
+    #[test]
+    fn chinese_remainder_with_common_factors() {
+        let congruence = Congruence::new(3, 6).combine(Congruence::new(7, 10));
+        assert_eq!(congruence, Some(Congruence::new(27, 30)));
+
+        let congruence = congruence.unwrap();
+        assert!(congruence.contains(57));
+        assert_eq!(congruence.smallest_at_least(28), 57);
+        assert_eq!(Congruence::new(0, 2).combine(Congruence::new(3, 6)), None);
+    }
+}
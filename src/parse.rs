@@ -0,0 +1,162 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// An error in the puzzle input, pointing at the offending text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The day whose input is invalid.
+    pub day: u8,
+    /// The line and column of the offending text, starting from 1, or 0 if it could not be
+    /// located in the input.
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, which is empty when something is missing.
+    pub text: String,
+    /// What was expected instead, e.g. "a number".
+    pub expected: String,
+    // Where the offending text starts in memory, to find its line and column once the input is
+    // known.
+    address: usize,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl ParseError {
+    /// Creates an error for the offending text, which must be a slice of the puzzle input so that
+    /// [`ParseError::locate`] can find its line and column.
+    pub fn new(text: &str, expected: impl Into<String>) -> Self {
+        Self {
+            day: 0,
+            line: 0,
+            column: 0,
+            text: text.to_owned(),
+            expected: expected.into(),
+            address: text.as_ptr() as usize,
+        }
+    }
+
+    /// Sets the day of the error, and the line and column of its text in the input.
+    ///
+    /// Parsers don't know either, so [`Solver::solve`](crate::Solver::solve) locates the errors
+    /// they return.
+    pub fn locate(mut self, day: u8, input: &str) -> Self {
+        self.day = day;
+
+        let before = self
+            .address
+            .checked_sub(input.as_ptr() as usize)
+            .and_then(|offset| input.get(..offset));
+        if let Some(before) = before {
+            let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+            self.line = before.matches('\n').count() + 1;
+            self.column = before[line_start..].chars().count() + 1;
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid input of day {}", self.day)?;
+        if self.line > 0 {
+            write!(f, " at line {}, column {}", self.line, self.column)?;
+        }
+        write!(f, ": expected {}, ", self.expected)?;
+        if self.text.is_empty() {
+            write!(f, "found nothing")
+        } else {
+            write!(f, "found `{}`", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses the text as a number.
+pub fn number<T: FromStr>(text: &str) -> ParseResult<T> {
+    text.parse().map_err(|_| ParseError::new(text, "a number"))
+}
+
+/// Parses the whitespace-separated numbers of the text.
+pub fn numbers<T: FromStr>(text: &str) -> ParseResult<Vec<T>> {
+    text.split_whitespace().map(number).collect()
+}
+
+/// Splits the text around the first occurrence of the delimiter.
+pub fn split_once<'a>(text: &'a str, delimiter: &str) -> ParseResult<(&'a str, &'a str)> {
+    text.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(text, format!("`{delimiter}`")))
+}
+
+/// Removes the prefix from the text.
+pub fn strip_prefix<'a>(text: &'a str, prefix: &str) -> ParseResult<&'a str> {
+    text.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(text, format!("`{prefix}`")))
+}
+
+/// Takes the next item of the iterator, e.g. the next line of the input. A missing item is
+/// reported at the end of `previous`, the text preceding it.
+pub fn next<'a>(
+    items: &mut impl Iterator<Item = &'a str>,
+    previous: &'a str,
+    expected: &str,
+) -> ParseResult<&'a str> {
+    items
+        .next()
+        .ok_or_else(|| ParseError::new(&previous[previous.len()..], expected))
+}
+
+/// Parses each character of the text, e.g. the tiles of a row of a grid.
+pub fn chars<'a, T: 'a>(
+    text: &'a str,
+    expected: &'a str,
+    parse: impl Fn(char) -> Option<T> + 'a,
+) -> impl Iterator<Item = ParseResult<T>> + 'a {
+    text.char_indices().map(move |(index, char)| {
+        parse(char).ok_or_else(|| ParseError::new(&text[index..index + char.len_utf8()], expected))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_is_located_in_the_input() {
+        let input = "1 2\n3 x 5\n";
+        let error = numbers::<u32>(input.lines().nth(1).unwrap())
+            .unwrap_err()
+            .locate(4, input);
+
+        assert_eq!((error.day, error.line, error.column), (4, 2, 3));
+        assert_eq!(
+            error.to_string(),
+            "invalid input of day 4 at line 2, column 3: expected a number, found `x`"
+        );
+    }
+
+    #[test]
+    fn missing_item_is_located_after_the_previous_one() {
+        let input = "header";
+        let mut lines = input.lines();
+        let header = lines.next().unwrap();
+        let error = next(&mut lines, header, "a line")
+            .unwrap_err()
+            .locate(1, input);
+
+        assert_eq!((error.line, error.column), (1, 7));
+        assert_eq!(error.text, "");
+    }
+
+    #[test]
+    fn error_outside_of_the_input_is_not_located() {
+        let error = number::<u32>("x").unwrap_err().locate(1, "1\n2\n");
+
+        assert_eq!((error.line, error.column), (0, 0));
+        assert_eq!(
+            error.to_string(),
+            "invalid input of day 1: expected a number, found `x`"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use crate::parse::ParseResult;
+use crate::solver::{Answer, Part, Solver};
+
+/// A registered solver, with its type erased so that solvers of all days can be stored together.
+#[derive(Clone, Copy)]
+pub struct SolverEntry {
+    pub year: u16,
+    pub day: u8,
+    solve: fn(Part, &str) -> ParseResult<Answer>,
+    validate: fn(&str) -> ParseResult<()>,
+    terminates: fn(Part) -> bool,
+}
+
+impl SolverEntry {
+    fn new<S: Solver>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            solve: S::solve,
+            validate: S::validate,
+            terminates: S::terminates,
+        }
+    }
+
+    pub fn solve(&self, part: Part, input: &str) -> ParseResult<Answer> {
+        (self.solve)(part, input)
+    }
+
+    pub fn validate(&self, input: &str) -> ParseResult<()> {
+        (self.validate)(input)
+    }
+
+    pub fn terminates(&self, part: Part) -> bool {
+        (self.terminates)(part)
+    }
+}
+
+/// Solvers keyed by the year and day of the problem they solve.
+#[derive(Default)]
+pub struct Registry {
+    solvers: BTreeMap<(u16, u8), SolverEntry>,
+}
+
+impl Registry {
+    /// Registers the solver under its year and day.
+    ///
+    /// Panics if a solver is already registered for that year and day.
+    pub fn register<S: Solver>(&mut self) -> &mut Self {
+        let entry = SolverEntry::new::<S>();
+        let previous_entry = self.solvers.insert((entry.year, entry.day), entry);
+        assert!(
+            previous_entry.is_none(),
+            "A solver is already registered for day {} of {}",
+            entry.day,
+            entry.year
+        );
+
+        self
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&SolverEntry> {
+        self.solvers.get(&(year, day))
+    }
+
+    /// Iterates through the solvers, ordered by year and then by day.
+    pub fn iter(&self) -> impl Iterator<Item = &SolverEntry> {
+        self.solvers.values()
+    }
+
+    /// Iterates through the solvers of the given year, ordered by day.
+    pub fn iter_year(&self, year: u16) -> impl Iterator<Item = &SolverEntry> {
+        self.solvers
+            .range((year, u8::MIN)..=(year, u8::MAX))
+            .map(|(_, entry)| entry)
+    }
+}
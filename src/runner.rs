@@ -0,0 +1,208 @@
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2023::registry::SolverEntry;
+use advent_of_code_2023::{Answer, ParseError, Part};
+use serde_json::{json, Value};
+
+// Solves the part and times it, unless it is known not to terminate or there is no input
+// to solve. An invalid input is reported instead of the answer.
+pub fn run(solver: &SolverEntry, part: Part, input: &io::Result<String>) -> Run {
+    let outcome = match input {
+        _ if !solver.terminates(part) => Outcome::Skipped,
+        Ok(input) => {
+            let start = Instant::now();
+            match solver.solve(part, input) {
+                Ok(answer) => Outcome::Solved {
+                    answer,
+                    elapsed: start.elapsed(),
+                },
+                Err(error) => Outcome::InvalidInput(error),
+            }
+        }
+        Err(error) => Outcome::NoInput(error.to_string()),
+    };
+
+    Run {
+        day: solver.day,
+        part,
+        outcome,
+    }
+}
+
+// Solves the parts of every solver on `jobs` threads, each taking the next part left to solve
+// until there are none. The runs are returned in the order of the solvers and parts, whichever
+// thread solved them.
+pub fn run_parallel(
+    solvers: &[(&SolverEntry, io::Result<String>)],
+    parts: &[Part],
+    jobs: usize,
+) -> Vec<Run> {
+    let tasks: Vec<_> = solvers
+        .iter()
+        .flat_map(|(solver, input)| parts.iter().map(move |part| (*solver, *part, input)))
+        .collect();
+    let next_task = AtomicUsize::new(0);
+
+    let (tx, rx) = channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(tasks.len()) {
+            let (tasks, next_task, tx) = (&tasks, &next_task, tx.clone());
+            scope.spawn(move || loop {
+                let index = next_task.fetch_add(1, Ordering::Relaxed);
+                let Some((solver, part, input)) = tasks.get(index) else {
+                    break;
+                };
+                tx.send((index, run(solver, *part, input))).unwrap();
+            });
+        }
+    });
+    drop(tx);
+
+    let mut runs: Vec<_> = rx.into_iter().collect();
+    runs.sort_unstable_by_key(|(index, _)| *index);
+    runs.into_iter().map(|(_, run)| run).collect()
+}
+
+pub enum Outcome {
+    Solved { answer: Answer, elapsed: Duration },
+    Skipped,
+    NoInput(String),
+    InvalidInput(ParseError),
+}
+
+pub struct Run {
+    pub day: u8,
+    pub part: Part,
+    pub outcome: Outcome,
+}
+
+impl Run {
+    pub fn answer(&self) -> Option<&Answer> {
+        match &self.outcome {
+            Outcome::Solved { answer, .. } => Some(answer),
+            Outcome::Skipped | Outcome::NoInput(_) | Outcome::InvalidInput(_) => None,
+        }
+    }
+
+    pub fn invalid_input(&self) -> bool {
+        matches!(self.outcome, Outcome::InvalidInput(_))
+    }
+
+    // The answer, or why there is none.
+    pub fn answer_cell(&self) -> String {
+        match &self.outcome {
+            Outcome::Solved { answer, .. } => answer.to_string(),
+            Outcome::Skipped => "(skipped: does not terminate)".to_owned(),
+            Outcome::NoInput(error) => format!("(no input: {error})"),
+            Outcome::InvalidInput(error) => format!("({error})"),
+        }
+    }
+
+    // Parts without an answer have a null answer and runtime, and the reason in `error`.
+    pub fn to_json(&self) -> Value {
+        let mut run = json!({
+            "day": self.day,
+            "part": self.part.number(),
+            "answer": null,
+            "elapsed_ns": null,
+        });
+        match &self.outcome {
+            Outcome::Solved { answer, elapsed } => {
+                run["answer"] = answer_to_json(answer);
+                run["elapsed_ns"] = json!(elapsed.as_nanos() as u64);
+            }
+            Outcome::Skipped => run["error"] = json!("skipped: does not terminate"),
+            Outcome::NoInput(error) => run["error"] = json!(format!("no input: {error}")),
+            Outcome::InvalidInput(error) => run["error"] = json!(error.to_string()),
+        }
+
+        run
+    }
+
+    fn elapsed_cell(&self) -> String {
+        match &self.outcome {
+            Outcome::Solved { elapsed, .. } => format!("{elapsed:.2?}"),
+            _ => "-".to_owned(),
+        }
+    }
+}
+
+pub fn print_runs(runs: &[Run]) {
+    let rows: Vec<_> = runs
+        .iter()
+        .map(|run| {
+            [
+                run.day.to_string(),
+                run.part.number().to_string(),
+                run.answer_cell(),
+                run.elapsed_cell(),
+            ]
+        })
+        .collect();
+    print_table(["Day", "Part", "Answer", "Time"], &rows, &[2]);
+
+    let total: Duration = runs
+        .iter()
+        .filter_map(|run| match run.outcome {
+            Outcome::Solved { elapsed, .. } => Some(elapsed),
+            _ => None,
+        })
+        .sum();
+    println!("\nTotal time: {total:.2?}");
+}
+
+// Numbers stay numbers, so that they don't need to be parsed again.
+fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Int(answer) => json!(answer),
+        Answer::UInt(answer) => json!(answer),
+        Answer::Text(answer) => json!(answer),
+        Answer::Empty => Value::Null,
+    }
+}
+
+// Prints a JSON object per line.
+pub fn print_json_lines(runs: &[Run]) {
+    for run in runs {
+        println!("{}", run.to_json());
+    }
+}
+
+// Prints the rows in aligned columns. Columns are right-aligned, except for the given ones.
+pub fn print_table<const N: usize>(
+    headers: [&str; N],
+    rows: &[[String; N]],
+    left_aligned_columns: &[usize],
+) {
+    let mut widths = headers.map(|header| header.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |row: [&str; N]| {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                if left_aligned_columns.contains(&column) {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    };
+
+    print_row(headers);
+    println!("{}", widths.map(|width| "-".repeat(width)).join("-+-"));
+    for row in rows {
+        print_row(row.each_ref().map(String::as_str));
+    }
+}
@@ -1,13 +1,100 @@
-use crate::args::Part;
+use std::fmt;
 
-pub trait Solver {
-    fn solve(part: Part) {
-        match part {
-            Part::Part1 => Self::solve_part1(),
-            Part::Part2 => Self::solve_part2(),
+use crate::parse::ParseResult;
+
+/// One of the two parts of a day's problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    Part1,
+    Part2,
+}
+
+impl Part {
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::Part1 => 1,
+            Part::Part2 => 2,
         }
     }
+}
+
+/// The answer to one part of a day's problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    // Some parts have no problem to solve (e.g. day 25's part 2).
+    Empty,
+}
 
-    fn solve_part1();
-    fn solve_part2();
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(answer) => write!(f, "{answer}"),
+            Answer::UInt(answer) => write!(f, "{answer}"),
+            Answer::Text(answer) => write!(f, "{answer}"),
+            Answer::Empty => write!(f, "N/A"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($variant:ident as $target:ty: $($source:ty),+) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )+
+    };
+}
+
+impl_answer_from!(Int as i64: i32, i64);
+impl_answer_from!(UInt as u64: u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+/// Solves both parts of a day's problem, given the puzzle input.
+pub trait Solver {
+    /// The year and day of the problem, which the solver is registered under.
+    const YEAR: u16;
+    const DAY: u8;
+
+    /// Solves the part, or reports where the input is invalid.
+    fn solve(part: Part, input: &str) -> ParseResult<Answer> {
+        let answer = match part {
+            Part::Part1 => Self::solve_part1(input),
+            Part::Part2 => Self::solve_part2(input),
+        };
+        answer.map_err(|error| error.locate(Self::DAY, input))
+    }
+
+    fn solve_part1(input: &str) -> ParseResult<Answer>;
+    fn solve_part2(input: &str) -> ParseResult<Answer>;
+
+    /// Checks that the input is valid without solving it, or reports where it is invalid.
+    fn validate(input: &str) -> ParseResult<()> {
+        Self::parse(input).map_err(|error| error.locate(Self::DAY, input))
+    }
+
+    /// Parses the input the way the solvers of both parts do, discarding the result.
+    fn parse(input: &str) -> ParseResult<()>;
+
+    /// Whether solving the given part finishes within a reasonable amount of time. Parts that
+    /// don't are skipped when solving all days at once.
+    fn terminates(_part: Part) -> bool {
+        true
+    }
 }
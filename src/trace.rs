@@ -0,0 +1,30 @@
+//! Tracing of the intermediate state of the solvers, to debug them without editing them.
+//!
+//! Tracing is off by default. While it is off, [`trace!`](crate::trace!) costs a single atomic
+//! load, and its arguments aren't evaluated.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns tracing on or off for the whole program.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Prints a message to stderr if tracing is on, prefixed by the module tracing it, e.g.
+/// `[day17] ...`. Takes the same arguments as `format!`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        if $crate::trace::enabled() {
+            let module = module_path!().rsplit("::").next().unwrap();
+            eprintln!("[{module}] {}", format_args!($($arg)+));
+        }
+    };
+}
@@ -0,0 +1,110 @@
+use std::io;
+use std::path::Path;
+
+use advent_of_code_2023::Part;
+use serde_json::json;
+use toml::{Table, Value};
+
+use crate::inputs;
+use crate::runner::{self, Outcome, Run};
+
+// Reads the recorded answers of the day, which sit next to its input in the inputs directory since
+// each input has its own answers.
+pub fn read_answers(inputs_dir: &Path, year: u16, day: u8) -> io::Result<Table> {
+    let path = inputs::answers_path(inputs_dir, year, day);
+    let answers = std::fs::read_to_string(&path)
+        .map_err(|error| io::Error::new(error.kind(), format!("{}: {error}", path.display())))?;
+    answers
+        .parse()
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+// The recorded answer of the part, formatted like the solver's answer.
+fn expected_answer(answers: &Table, part: Part) -> Option<String> {
+    match answers.get(&format!("part{}", part.number()))? {
+        Value::String(answer) => Some(answer.clone()),
+        answer => Some(answer.to_string()),
+    }
+}
+
+pub struct Verification {
+    pub run: Run,
+    pub expected: Result<Option<String>, String>,
+}
+
+impl Verification {
+    pub fn new(run: Run, answers: &io::Result<Table>) -> Self {
+        let expected = match answers {
+            Ok(answers) => Ok(expected_answer(answers, run.part)),
+            Err(error) => Err(error.to_string()),
+        };
+
+        Self { run, expected }
+    }
+
+    // A verification fails if the solver's answer differs from the recorded one, if the input is
+    // missing or invalid, or if the day's answers can't be read, so that nothing passes unsolved.
+    // Parts without a recorded answer or skipped parts are reported, but not failed.
+    pub fn failed(&self) -> bool {
+        match (&self.expected, &self.run.outcome, self.run.answer()) {
+            (_, Outcome::NoInput(_) | Outcome::InvalidInput(_), _) | (Err(_), _, _) => true,
+            (Ok(Some(expected)), _, Some(answer)) => *expected != answer.to_string(),
+            _ => false,
+        }
+    }
+
+    // The run, with the recorded answer and whether it passed. Parts that were neither passed
+    // nor failed have a null `passed`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut verification = self.run.to_json();
+        let expected = self.expected.as_ref().ok().cloned().flatten();
+        let passed = match (&expected, self.run.answer()) {
+            (Some(_), Some(_)) => json!(!self.failed()),
+            _ if self.failed() => json!(false),
+            _ => serde_json::Value::Null,
+        };
+        verification["expected"] = json!(expected);
+        verification["passed"] = passed;
+
+        verification
+    }
+
+    fn result_cell(&self) -> String {
+        match (&self.expected, &self.run.outcome) {
+            (_, Outcome::NoInput(error)) => format!("FAIL: no input: {error}"),
+            (Err(error), _) => format!("FAIL: no answers: {error}"),
+            (_, Outcome::Skipped) => "skipped: does not terminate".to_owned(),
+            (_, Outcome::InvalidInput(error)) => format!("FAIL: {error}"),
+            (Ok(None), _) => "no recorded answer".to_owned(),
+            _ if self.failed() => "FAIL".to_owned(),
+            _ => "pass".to_owned(),
+        }
+    }
+}
+
+pub fn print_verifications(verifications: &[Verification]) {
+    let rows: Vec<_> = verifications
+        .iter()
+        .map(|verification| {
+            let run = &verification.run;
+            [
+                run.day.to_string(),
+                run.part.number().to_string(),
+                match &verification.expected {
+                    Ok(Some(expected)) => expected.clone(),
+                    _ => "-".to_owned(),
+                },
+                run.answer().map_or("-".to_owned(), ToString::to_string),
+                verification.result_cell(),
+            ]
+        })
+        .collect();
+    runner::print_table(
+        ["Day", "Part", "Expected", "Answer", "Result"],
+        &rows,
+        &[2, 3, 4],
+    );
+
+    let failures = verifications.iter().filter(|v| v.failed()).count();
+    println!("\n{failures} of {} parts failed.", verifications.len());
+}